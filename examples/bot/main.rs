@@ -3,9 +3,9 @@
 #![warn(clippy::all)]
 
 use na::Vector2;
-use std::{error::Error, f32::consts::PI};
+use std::f32::consts::PI;
 
-fn main() -> Result<(), Box<dyn Error>> {
+fn main() -> Result<(), rlbot::Error> {
     rlbot::run_bot(MyBot { player_index: 0 })
 }
 
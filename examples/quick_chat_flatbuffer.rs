@@ -27,18 +27,20 @@ fn main() -> Result<(), Box<dyn Error>> {
 
 fn build_chat() -> flatbuffers::FlatBufferBuilder<'static> {
     let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(64);
-    let root = rlbot::flat::QuickChat::create(&mut builder, &rlbot::flat::QuickChatArgs {
-        quickChatSelection: select_chat(),
-        playerIndex: 0,
-        teamOnly: false,
-        ..Default::default()
-    });
+    let root = rlbot::flat::QuickChat::create(
+        &mut builder,
+        &rlbot::flat::QuickChatArgs {
+            quickChatSelection: select_chat(),
+            playerIndex: 0,
+            teamOnly: false,
+            ..Default::default()
+        },
+    );
     builder.finish(root, None);
     builder
 }
 
 fn select_chat() -> rlbot::flat::QuickChatSelection {
-    let max_chat = rlbot::flat::QuickChatSelection::Custom_Compliments_Pro as i8 + 1;
-    let chat_index = rand::thread_rng().gen_range(0, max_chat);
-    unsafe { std::mem::transmute(chat_index) }
+    let chats: Vec<_> = rlbot::QuickChatSelection::all().collect();
+    *chats.choose(&mut rand::thread_rng()).unwrap()
 }
@@ -34,7 +34,7 @@ fn main() -> Result<(), Box<dyn Error>> {
         };
 
         let mut group = rlbot.begin_render_group(0);
-        let green = group.color_rgb(0, 255, 0);
+        let green = rlbot::Color::GREEN;
         group.draw_string_2d(
             (40.0, 20.0),
             (2, 2),
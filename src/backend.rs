@@ -0,0 +1,143 @@
+//! The seam between this crate and whatever is on the other side of RLBot's
+//! core interface.
+
+use crate::ffi;
+
+/// A source of game data and a sink for commands, speaking the same language
+/// as `RLBot_Core_Interface.dll`.
+///
+/// Normally this is the DLL itself, which is loaded by [`init`](crate::init).
+/// Other implementations can be passed to
+/// [`init_with_backend`](crate::init_with_backend) to drive an [`RLBot`]
+/// without the game, e.g. [`MockBackend`](crate::MockBackend) for tests.
+///
+/// All buffers are raw flatbuffers, exactly as they would be produced or
+/// consumed by the DLL.
+///
+/// [`RLBot`]: crate::RLBot
+pub trait CoreBackend: Send {
+    /// Returns true once the backend is ready to accept calls.
+    fn is_initialized(&self) -> bool;
+
+    /// Returns the current [`flat::FieldInfo`](crate::flat::FieldInfo), if
+    /// any.
+    fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>>;
+
+    /// Returns the current
+    /// [`flat::GameTickPacket`](crate::flat::GameTickPacket), if any.
+    fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>>;
+
+    /// Like
+    /// [`update_live_data_packet_flatbuffer`](CoreBackend::update_live_data_packet_flatbuffer),
+    /// but writes into `buf`, so its allocation can be reused from one packet
+    /// to the next. Returns false (and leaves `buf` alone) if there is no
+    /// packet.
+    fn update_live_data_packet_flatbuffer_into(&self, buf: &mut Vec<u8>) -> bool {
+        match self.update_live_data_packet_flatbuffer() {
+            Some(packet) => {
+                buf.clear();
+                buf.extend_from_slice(&packet);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the current
+    /// [`flat::RigidBodyTick`](crate::flat::RigidBodyTick), if any.
+    fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>>;
+
+    /// Returns the current
+    /// [`flat::BallPrediction`](crate::flat::BallPrediction), if any.
+    fn get_ball_prediction(&self) -> Option<Vec<u8>>;
+
+    /// Sets the game state from a
+    /// [`flat::DesiredGameState`](crate::flat::DesiredGameState).
+    fn set_game_state(&self, desired_game_state_buffer: &[u8]) -> ffi::RLBotCoreStatus;
+
+    /// Starts a match from a
+    /// [`flat::MatchSettings`](crate::flat::MatchSettings).
+    fn start_match_flatbuffer(&self, match_settings_buffer: &[u8]) -> ffi::RLBotCoreStatus;
+
+    /// Sends a [`flat::QuickChat`](crate::flat::QuickChat).
+    fn send_quick_chat(&self, quick_chat_buffer: &[u8]) -> ffi::RLBotCoreStatus;
+
+    /// Returns a [`flat::QuickChatMessages`](crate::flat::QuickChatMessages)
+    /// holding the chats that the given player can see (i.e. excluding the
+    /// other team's team-only chats) whose `messageIndex` is greater than
+    /// `last_message_index`, if any.
+    ///
+    /// By default this returns `None`, as if nobody ever chats.
+    fn receive_chat(
+        &self,
+        _player_index: i32,
+        _team: i32,
+        _last_message_index: i32,
+    ) -> Option<Vec<u8>> {
+        None
+    }
+
+    /// Sends a [`flat::PlayerInput`](crate::flat::PlayerInput).
+    fn update_player_input_flatbuffer(&self, player_input_buffer: &[u8]) -> ffi::RLBotCoreStatus;
+
+    /// Renders a [`flat::RenderGroup`](crate::flat::RenderGroup).
+    fn render_group(&self, render_group_buffer: &[u8]) -> ffi::RLBotCoreStatus;
+
+    /// Struct-based equivalent of
+    /// [`update_field_info_flatbuffer`](CoreBackend::update_field_info_flatbuffer).
+    ///
+    /// Backends other than the DLL are not expected to support the
+    /// struct-based calls, so by default this returns
+    /// [`NotInitialized`](ffi::RLBotCoreStatus::NotInitialized).
+    fn update_field_info(&self, _field_info: &mut ffi::FieldInfo) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+
+    /// Struct-based equivalent of
+    /// [`update_live_data_packet_flatbuffer`](CoreBackend::update_live_data_packet_flatbuffer).
+    fn update_live_data_packet(&self, _packet: &mut ffi::LiveDataPacket) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+
+    /// Struct-based equivalent of
+    /// [`update_rigid_body_tick_flatbuffer`](CoreBackend::update_rigid_body_tick_flatbuffer).
+    fn update_rigid_body_tick(&self, _tick: &mut ffi::RigidBodyTick) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+
+    /// Struct-based equivalent of
+    /// [`start_match_flatbuffer`](CoreBackend::start_match_flatbuffer).
+    fn start_match(&self, _match_settings: ffi::MatchSettings) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+
+    /// Struct-based equivalent of
+    /// [`send_quick_chat`](CoreBackend::send_quick_chat).
+    fn send_chat(
+        &self,
+        _quick_chat_preset: ffi::QuickChatPreset,
+        _player_index: i32,
+        _team_only: bool,
+    ) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+
+    /// Struct-based equivalent of
+    /// [`update_player_input_flatbuffer`](CoreBackend::update_player_input_flatbuffer).
+    fn update_player_input(
+        &self,
+        _player_input: ffi::PlayerInput,
+        _player_index: i32,
+    ) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+
+    /// Struct-based equivalent of
+    /// [`get_ball_prediction`](CoreBackend::get_ball_prediction).
+    fn get_ball_prediction_struct(
+        &self,
+        _result: &mut ffi::BallPredictionPacket,
+    ) -> ffi::RLBotCoreStatus {
+        ffi::RLBotCoreStatus::NotInitialized
+    }
+}
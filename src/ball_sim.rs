@@ -0,0 +1,421 @@
+//! A pure-Rust ball simulation, for predicting the ball's path without the
+//! framework's `BallPrediction.exe`.
+
+use crate::game::{
+    BallPrediction, CollisionShape, GameTickPacket, Physics, PredictionSlice, Rotator, Vector3,
+};
+
+type Vec3 = [f32; 3];
+
+/// The game's physics runs at this many ticks per second.
+const TICK_RATE: f32 = 120.0;
+
+/// The radius of the standard soccar ball.
+const BALL_RADIUS: f32 = 91.25;
+/// The ball loses this fraction of its velocity per second to air resistance.
+const DRAG: f32 = 0.0305;
+const MAX_SPEED: f32 = 6000.0;
+const MAX_ANGULAR_SPEED: f32 = 6.0;
+/// The fraction of the velocity perpendicular to a surface that survives a
+/// bounce.
+const RESTITUTION: f32 = 0.6;
+/// Friction between the ball and a surface during a bounce.
+const FRICTION: f32 = 0.285;
+/// Relates friction to the ratio of perpendicular to sliding speed.
+const FRICTION_SCALE: f32 = 2.0;
+/// How strongly friction during a bounce converts into spin.
+const SPIN_TRANSFER: f32 = 0.0003;
+
+const ARENA_HALF_WIDTH: f32 = 4096.0;
+const ARENA_HALF_LENGTH: f32 = 5120.0;
+const ARENA_HEIGHT: f32 = 2044.0;
+/// The 45° corners are the planes where `|x| + |y|` equals this.
+const ARENA_CORNER: f32 = 8064.0;
+const GOAL_HALF_WIDTH: f32 = 892.755;
+const GOAL_HEIGHT: f32 = 642.775;
+const GOAL_DEPTH: f32 = 880.0;
+
+/// Predicts the path of the ball in a standard soccar arena.
+///
+/// This is a simplified model, and it will drift from the real game over
+/// time. It accounts for gravity, air resistance, and bounces (including
+/// spin) off the floor, ceiling, walls, corners, and the inside of the goals.
+/// The curved ramps between surfaces are treated as sharp edges, and cars are
+/// ignored completely.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let rlbot = rlbot::init()?;
+/// let packet = rlbot.packeteer().next()?;
+/// if let Some(prediction) = rlbot::BallPredictor::new().predict_packet(&packet) {
+///     let last = prediction.slices.last().unwrap();
+///     println!("In six seconds the ball will be at z={}", last.physics.location.z);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct BallPredictor {
+    world_gravity_z: f32,
+    duration: f32,
+    radius: f32,
+}
+
+impl Default for BallPredictor {
+    fn default() -> Self {
+        Self {
+            world_gravity_z: -650.0,
+            duration: 6.0,
+            radius: BALL_RADIUS,
+        }
+    }
+}
+
+impl BallPredictor {
+    /// Constructs a new `BallPredictor`, which predicts six seconds at 120Hz
+    /// under standard gravity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the gravity acceleration. This has the same meaning as
+    /// [`GameInfo::world_gravity_z`](crate::GameInfo::world_gravity_z).
+    pub fn world_gravity_z(mut self, world_gravity_z: f32) -> Self {
+        self.world_gravity_z = world_gravity_z;
+        self
+    }
+
+    /// Sets how many seconds into the future to predict.
+    pub fn duration(mut self, duration: f32) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Sets the radius of the ball. The default is the standard soccar ball.
+    pub fn radius(mut self, radius: f32) -> Self {
+        self.radius = radius;
+        self
+    }
+
+    /// Predicts the ball's path starting from `physics`, which is the state of
+    /// the ball at `game_seconds`.
+    ///
+    /// The first slice is the starting state itself, and each following slice
+    /// is one physics tick (1/120th of a second) later. The ball's rotation is
+    /// not simulated, and is copied from `physics` into every slice.
+    pub fn predict(&self, physics: &Physics, game_seconds: f32) -> BallPrediction {
+        let dt = 1.0 / TICK_RATE;
+        let steps = (self.duration * TICK_RATE).round() as usize;
+
+        let mut state = BallState {
+            radius: self.radius,
+            location: from_vector3(&physics.location),
+            velocity: from_vector3(&physics.velocity),
+            angular_velocity: from_vector3(&physics.angular_velocity),
+        };
+        let mut prediction = BallPrediction {
+            slices: Default::default(),
+            _non_exhaustive: (),
+        };
+        prediction
+            .slices
+            .push(state.to_slice(&physics.rotation, game_seconds));
+
+        for i in 1..=steps {
+            state.step(dt, self.world_gravity_z);
+            prediction
+                .slices
+                .push(state.to_slice(&physics.rotation, game_seconds + i as f32 * dt));
+        }
+        prediction
+    }
+
+    /// Predicts the ball's path starting from the ball in a packet, using the
+    /// packet's gravity. Returns `None` if the packet does not contain a ball.
+    ///
+    /// If the packet reports a spherical ball, its radius is used as well.
+    /// Other shapes (the cube and the puck) are still simulated as a sphere of
+    /// the configured radius.
+    pub fn predict_packet(&self, packet: &GameTickPacket) -> Option<BallPrediction> {
+        let ball = packet.ball.as_ref()?;
+        let mut predictor = self
+            .clone()
+            .world_gravity_z(packet.game_info.world_gravity_z);
+        if let Some(CollisionShape::Sphere(sphere)) = ball.shape {
+            predictor = predictor.radius(sphere.radius());
+        }
+        Some(predictor.predict(&ball.physics, packet.game_info.seconds_elapsed))
+    }
+}
+
+struct BallState {
+    radius: f32,
+    location: Vec3,
+    velocity: Vec3,
+    angular_velocity: Vec3,
+}
+
+impl BallState {
+    fn step(&mut self, dt: f32, gravity_z: f32) {
+        self.velocity[2] += gravity_z * dt;
+        self.velocity = scale(self.velocity, 1.0 - DRAG * dt);
+        self.velocity = clamp_length(self.velocity, MAX_SPEED);
+        self.location = add(self.location, scale(self.velocity, dt));
+
+        // A ball wedged into an edge can touch two surfaces in one tick.
+        for _ in 0..2 {
+            match deepest_contact(self.location, self.radius) {
+                Some((normal, depth)) => self.bounce(normal, depth),
+                None => break,
+            }
+        }
+
+        self.angular_velocity = clamp_length(self.angular_velocity, MAX_ANGULAR_SPEED);
+    }
+
+    fn bounce(&mut self, normal: Vec3, depth: f32) {
+        self.location = add(self.location, scale(normal, depth));
+
+        let normal_speed = dot(self.velocity, normal);
+        if normal_speed >= 0.0 {
+            return;
+        }
+
+        let v_perp = scale(normal, normal_speed);
+        let v_para = sub(self.velocity, v_perp);
+        let v_spin = scale(cross(normal, self.angular_velocity), self.radius);
+        let slip = add(v_para, v_spin);
+        let slip_speed = length(slip);
+
+        let delta_perp = scale(v_perp, -(1.0 + RESTITUTION));
+        let delta_para = if slip_speed > 0.0 {
+            let ratio = length(v_perp) / slip_speed;
+            scale(slip, -(FRICTION_SCALE * ratio).min(1.0) * FRICTION)
+        } else {
+            [0.0; 3]
+        };
+
+        self.velocity = add(self.velocity, add(delta_perp, delta_para));
+        self.angular_velocity = add(
+            self.angular_velocity,
+            scale(cross(delta_para, normal), SPIN_TRANSFER * self.radius),
+        );
+    }
+
+    fn to_slice(&self, rotation: &Rotator, game_seconds: f32) -> PredictionSlice {
+        PredictionSlice {
+            game_seconds,
+            physics: Physics {
+                location: to_vector3(self.location),
+                rotation: Rotator {
+                    pitch: rotation.pitch,
+                    yaw: rotation.yaw,
+                    roll: rotation.roll,
+                },
+                velocity: to_vector3(self.velocity),
+                angular_velocity: to_vector3(self.angular_velocity),
+                _non_exhaustive: (),
+            },
+            _non_exhaustive: (),
+        }
+    }
+}
+
+/// Finds the surface the ball is pushed into the furthest, if any. Returns the
+/// surface normal (pointing into the playable space) and how deep the ball is.
+fn deepest_contact(location: Vec3, radius: f32) -> Option<(Vec3, f32)> {
+    let [x, y, z] = location;
+    let in_goal_mouth = x.abs() < GOAL_HALF_WIDTH && z < GOAL_HEIGHT;
+    let sx = x.signum();
+    let sy = y.signum();
+    let diagonal = std::f32::consts::FRAC_1_SQRT_2;
+
+    // Each surface is a plane given by its normal and its distance from the
+    // origin along that normal.
+    let mut planes = vec![
+        ([0.0, 0.0, 1.0], 0.0),
+        ([0.0, 0.0, -1.0], -ARENA_HEIGHT),
+        ([-sx, 0.0, 0.0], -ARENA_HALF_WIDTH),
+        (
+            [-sx * diagonal, -sy * diagonal, 0.0],
+            -ARENA_CORNER * diagonal,
+        ),
+    ];
+    if y.abs() > ARENA_HALF_LENGTH {
+        // Inside a goal.
+        planes.push(([-sx, 0.0, 0.0], -GOAL_HALF_WIDTH));
+        planes.push(([0.0, 0.0, -1.0], -GOAL_HEIGHT));
+        planes.push(([0.0, -sy, 0.0], -(ARENA_HALF_LENGTH + GOAL_DEPTH)));
+    } else if !in_goal_mouth {
+        planes.push(([0.0, -sy, 0.0], -ARENA_HALF_LENGTH));
+    }
+
+    planes
+        .into_iter()
+        .map(|(normal, offset)| (normal, radius - (dot(location, normal) - offset)))
+        .filter(|&(_, depth)| depth > 0.0)
+        .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+}
+
+fn from_vector3(v: &Vector3) -> Vec3 {
+    [v.x, v.y, v.z]
+}
+
+fn to_vector3([x, y, z]: Vec3) -> Vector3 {
+    Vector3 { x, y, z }
+}
+
+fn add(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] + b[0], a[1] + b[1], a[2] + b[2]]
+}
+
+fn sub(a: Vec3, b: Vec3) -> Vec3 {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+fn scale(a: Vec3, s: f32) -> Vec3 {
+    [a[0] * s, a[1] * s, a[2] * s]
+}
+
+fn dot(a: Vec3, b: Vec3) -> f32 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+fn cross(a: Vec3, b: Vec3) -> Vec3 {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+fn length(a: Vec3) -> f32 {
+    dot(a, a).sqrt()
+}
+
+fn clamp_length(a: Vec3, max: f32) -> Vec3 {
+    let len = length(a);
+    if len > max {
+        scale(a, max / len)
+    } else {
+        a
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        ball_sim::{BallPredictor, ARENA_HALF_LENGTH, BALL_RADIUS, GOAL_DEPTH},
+        game::{Physics, Rotator, Vector3},
+    };
+
+    fn physics(location: (f32, f32, f32), velocity: (f32, f32, f32)) -> Physics {
+        Physics {
+            location: Vector3 {
+                x: location.0,
+                y: location.1,
+                z: location.2,
+            },
+            rotation: Rotator::default(),
+            velocity: Vector3 {
+                x: velocity.0,
+                y: velocity.1,
+                z: velocity.2,
+            },
+            angular_velocity: Vector3 {
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            },
+            _non_exhaustive: (),
+        }
+    }
+
+    #[test]
+    fn slices_at_120hz() {
+        let prediction =
+            BallPredictor::new().predict(&physics((0.0, 0.0, 93.0), (0.0, 0.0, 0.0)), 10.0);
+        assert_eq!(prediction.slices.len(), 721);
+        assert_eq!(prediction.slices[0].game_seconds, 10.0);
+        assert!((prediction.slices[120].game_seconds - 11.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn free_fall() {
+        let prediction = BallPredictor::new()
+            .duration(0.5)
+            .predict(&physics((0.0, 0.0, 1000.0), (0.0, 0.0, 0.0)), 0.0);
+        let end = &prediction.slices.last().unwrap().physics;
+        // Drag makes this slightly slower than 650 * 0.5.
+        assert!(end.velocity.z < -300.0 && end.velocity.z > -325.0);
+        assert!(end.location.z < 1000.0 - 75.0 && end.location.z > 1000.0 - 85.0);
+    }
+
+    #[test]
+    fn gravity_is_configurable() {
+        let prediction = BallPredictor::new()
+            .world_gravity_z(0.0)
+            .duration(1.0)
+            .predict(&physics((0.0, 0.0, 1000.0), (0.0, 0.0, 0.0)), 0.0);
+        assert_eq!(prediction.slices.last().unwrap().physics.location.z, 1000.0);
+    }
+
+    #[test]
+    fn floor_bounce() {
+        let prediction = BallPredictor::new()
+            .world_gravity_z(0.0)
+            .duration(1.0)
+            .predict(&physics((0.0, 0.0, 300.0), (0.0, 0.0, -1000.0)), 0.0);
+        let end = &prediction.slices.last().unwrap().physics;
+        assert!(end.velocity.z > 550.0 && end.velocity.z < 600.0);
+        assert!(prediction
+            .slices
+            .iter()
+            .all(|s| s.physics.location.z >= BALL_RADIUS - 1e-3));
+    }
+
+    #[test]
+    fn radius_is_configurable() {
+        let prediction = BallPredictor::new()
+            .radius(150.0)
+            .duration(2.0)
+            .predict(&physics((0.0, 0.0, 500.0), (0.0, 0.0, 0.0)), 0.0);
+        let min_z = prediction
+            .slices
+            .iter()
+            .map(|s| s.physics.location.z)
+            .fold(f32::INFINITY, f32::min);
+        assert!((150.0 - 1e-3..155.0).contains(&min_z));
+    }
+
+    #[test]
+    fn stays_in_arena() {
+        let prediction = BallPredictor::new().predict(
+            &physics((3000.0, 3000.0, 500.0), (4000.0, 3000.0, 1500.0)),
+            0.0,
+        );
+        for slice in &prediction.slices {
+            let l = &slice.physics.location;
+            assert!(l.x.abs() <= 4096.0 - BALL_RADIUS + 1.0);
+            assert!(l.y.abs() <= 5120.0 - BALL_RADIUS + 1.0);
+            assert!(l.z <= 2044.0 - BALL_RADIUS + 1.0);
+        }
+    }
+
+    #[test]
+    fn rolls_into_goal() {
+        let prediction = BallPredictor::new().duration(2.0).predict(
+            &physics((0.0, 4000.0, BALL_RADIUS), (0.0, 2000.0, 0.0)),
+            0.0,
+        );
+        let max_y = prediction
+            .slices
+            .iter()
+            .map(|s| s.physics.location.y)
+            .fold(0.0, f32::max);
+        assert!(max_y > ARENA_HALF_LENGTH + BALL_RADIUS);
+        assert!(max_y <= ARENA_HALF_LENGTH + GOAL_DEPTH - BALL_RADIUS + 1.0);
+    }
+}
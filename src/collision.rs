@@ -0,0 +1,107 @@
+//! Helpers for collision shapes.
+
+use crate::game::{BoxShape, CollisionShape, CylinderShape, SphereShape, Vector3};
+
+impl BoxShape {
+    /// Returns half the box's size along each local axis.
+    pub fn half_extents(&self) -> Vector3 {
+        Vector3::new(self.length / 2.0, self.width / 2.0, self.height / 2.0)
+    }
+
+    /// Returns true if `local`, a point in the owner's local frame (see e.g.
+    /// [`PlayerInfo::to_local`](crate::PlayerInfo::to_local)), lies within the
+    /// box.
+    pub fn contains(&self, local: Vector3) -> bool {
+        let half = self.half_extents();
+        local.x.abs() <= half.x && local.y.abs() <= half.y && local.z.abs() <= half.z
+    }
+}
+
+impl SphereShape {
+    /// Returns the sphere's radius.
+    pub fn radius(&self) -> f32 {
+        self.diameter / 2.0
+    }
+
+    /// Returns true if `local`, a point relative to the sphere's center, lies
+    /// within the sphere.
+    pub fn contains(&self, local: Vector3) -> bool {
+        local.length() <= self.radius()
+    }
+}
+
+impl CylinderShape {
+    /// Returns the radius of the cylinder's circular faces.
+    pub fn radius(&self) -> f32 {
+        self.diameter / 2.0
+    }
+
+    /// Returns true if `local`, a point in the owner's local frame relative to
+    /// the cylinder's center, lies within the cylinder.
+    pub fn contains(&self, local: Vector3) -> bool {
+        let radius = self.radius();
+        local.x * local.x + local.y * local.y <= radius * radius
+            && local.z.abs() <= self.height / 2.0
+    }
+}
+
+impl CollisionShape {
+    /// Returns the radius of the smallest sphere, centered on the shape, which
+    /// contains the whole shape. This is a cheap, conservative bound for
+    /// collision checks.
+    pub fn bounding_radius(&self) -> f32 {
+        match self {
+            CollisionShape::Box(shape) => shape.half_extents().length(),
+            CollisionShape::Sphere(shape) => shape.radius(),
+            CollisionShape::Cylinder(shape) => {
+                let radius = shape.radius();
+                let half_height = shape.height / 2.0;
+                (radius * radius + half_height * half_height).sqrt()
+            }
+        }
+    }
+
+    /// Returns true if `local`, a point in the owner's local frame relative to
+    /// the shape's center, lies within the shape.
+    pub fn contains(&self, local: Vector3) -> bool {
+        match self {
+            CollisionShape::Box(shape) => shape.contains(local),
+            CollisionShape::Sphere(shape) => shape.contains(local),
+            CollisionShape::Cylinder(shape) => shape.contains(local),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{BoxShape, CollisionShape, CylinderShape, SphereShape, Vector3};
+
+    #[test]
+    fn shapes_contain_points() {
+        let cube = CollisionShape::Box(BoxShape {
+            length: 200.0,
+            width: 200.0,
+            height: 200.0,
+            _non_exhaustive: (),
+        });
+        let ball = CollisionShape::Sphere(SphereShape {
+            diameter: 182.5,
+            _non_exhaustive: (),
+        });
+        let puck = CollisionShape::Cylinder(CylinderShape {
+            diameter: 228.0,
+            height: 62.5,
+            _non_exhaustive: (),
+        });
+        let corner = Vector3::new(95.0, 95.0, 95.0);
+        assert!(cube.contains(corner));
+        assert!(!ball.contains(corner));
+        assert!(puck.contains(Vector3::new(100.0, 0.0, 30.0)));
+        assert!(!puck.contains(Vector3::new(0.0, 0.0, 40.0)));
+
+        for shape in &[cube, ball, puck] {
+            let r = shape.bounding_radius();
+            assert!(!shape.contains(Vector3::new(r + 1.0, 0.0, 0.0)));
+        }
+    }
+}
@@ -0,0 +1,228 @@
+//! Drawing from anywhere in a bot, without a reference to `RLBot`.
+
+use crate::{render::RenderGroup, render_manager::RenderManager, rlbot::RLBot};
+use std::{
+    collections::BTreeMap,
+    mem,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, MutexGuard, PoisonError,
+    },
+};
+
+type Drawing = Box<dyn FnOnce(&mut RenderGroup<'_>) + Send>;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+/// Whether some game loop has claimed the queue.
+static FLUSHER_ACTIVE: AtomicBool = AtomicBool::new(false);
+static STATE: Mutex<State> = Mutex::new(State {
+    disabled: Vec::new(),
+    pending: Vec::new(),
+});
+
+struct State {
+    /// Categories that have been switched off.
+    disabled: Vec<String>,
+    /// Drawings queued since the last flush.
+    pending: Vec<(&'static str, Drawing)>,
+}
+
+fn state() -> MutexGuard<'static, State> {
+    // The state is never left half-updated, so a panic elsewhere is harmless.
+    STATE.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Queues debug drawings from anywhere in a bot, without passing around a
+/// [`RenderGroup`] or a reference to [`RLBot`].
+///
+/// Each drawing is tagged with a category, such as `"path"` or `"targets"`.
+/// Every category is drawn in its own render group, so categories can be
+/// switched on and off independently while the bot runs.
+///
+/// [`run_bot`](crate::run_bot) and [`run_hive`](crate::run_hive) send
+/// everything that was queued once per packet, after the tick. They use a
+/// [`RenderManager`], so categories that look the same as last tick are not
+/// sent again. If RLBot rejects a drawing, the game loop carries on regardless.
+///
+/// The queue is shared by the whole process. If several game loops run in one
+/// process, only the first one started sends the drawings, under its own
+/// player index.
+///
+/// Debug drawing starts out disabled. While disabled,
+/// [`draw`](DebugDraw::draw) returns after checking a single flag, without
+/// allocating or taking a lock. Keep any expensive work inside the closure,
+/// since the closure is only ever called if its drawing will be sent.
+///
+/// # Example
+///
+/// ```no_run
+/// use rlbot::{Color, DebugDraw, GameTickPacket};
+///
+/// fn aim(packet: &GameTickPacket) {
+///     let ball = packet.ball.as_ref().unwrap().physics.location;
+///     DebugDraw::draw("targets", move |group| {
+///         group.draw_sphere_3d(ball, 100.0, 16, Color::YELLOW);
+///     });
+/// }
+///
+/// DebugDraw::set_enabled(true);
+/// // Too noisy for now.
+/// DebugDraw::set_category_enabled("targets", false);
+/// ```
+pub enum DebugDraw {}
+
+impl DebugDraw {
+    /// Returns whether debug drawing is enabled.
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+
+    /// Enables or disables all debug drawing. Disabling it clears everything
+    /// that was drawn at the next flush.
+    pub fn set_enabled(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed);
+    }
+
+    /// Returns whether drawings in the given category will be sent. This does
+    /// not take [`is_enabled`](DebugDraw::is_enabled) into account.
+    pub fn is_category_enabled(category: &str) -> bool {
+        !state().disabled.iter().any(|c| c == category)
+    }
+
+    /// Switches a category on or off. All categories start out on. Switching
+    /// one off clears what it drew at the next flush.
+    pub fn set_category_enabled(category: &str, enabled: bool) {
+        let mut state = state();
+        state.disabled.retain(|c| c != category);
+        if !enabled {
+            state.disabled.push(category.to_owned());
+        }
+    }
+
+    /// Queues a drawing in the given category. The closure is called with the
+    /// category's render group when the drawings are flushed, on the thread
+    /// running the game loop.
+    pub fn draw(category: &'static str, draw: impl FnOnce(&mut RenderGroup<'_>) + Send + 'static) {
+        if !Self::is_enabled() {
+            return;
+        }
+        let mut state = state();
+        if state.disabled.iter().any(|c| c == category) {
+            return;
+        }
+        state.pending.push((category, Box::new(draw)));
+    }
+}
+
+/// Sends queued debug drawings on behalf of a game loop.
+pub(crate) struct DebugDrawFlusher {
+    /// Whether this flusher claimed the queue. Only one can at a time.
+    active: bool,
+    /// The player index that render group ids are derived from, so that bots
+    /// drawing in the same category don't overwrite each other.
+    owner: i32,
+    renders: RenderManager,
+    /// The categories that have anything on screen.
+    drawn: Vec<&'static str>,
+}
+
+impl DebugDrawFlusher {
+    pub fn new(owner: i32) -> Self {
+        Self {
+            active: !FLUSHER_ACTIVE.swap(true, Ordering::SeqCst),
+            owner,
+            renders: RenderManager::new(),
+            drawn: Vec::new(),
+        }
+    }
+
+    /// Renders everything queued since the last flush, and clears categories
+    /// that drew nothing this time.
+    pub fn flush(&mut self, rlbot: &RLBot) {
+        if !self.active {
+            return;
+        }
+
+        // Drain the queue even while disabled, so that drawings queued just
+        // before drawing was disabled don't show up once it's enabled again.
+        let pending = mem::take(&mut state().pending);
+        let enabled = DebugDraw::is_enabled();
+        if !enabled && self.drawn.is_empty() {
+            return;
+        }
+
+        let mut categories: BTreeMap<&'static str, Vec<Drawing>> = BTreeMap::new();
+        if enabled {
+            for (category, drawing) in pending {
+                categories.entry(category).or_default().push(drawing);
+            }
+        }
+        for &category in &self.drawn {
+            categories.entry(category).or_default();
+        }
+
+        self.drawn.clear();
+        for (category, drawings) in categories {
+            let mut group = rlbot.begin_render_group(group_id(self.owner, category));
+            for drawing in drawings {
+                drawing(&mut group);
+            }
+            if !group.messages.is_empty() {
+                self.drawn.push(category);
+            }
+            // Debug drawing is best-effort, and shouldn't end the game loop.
+            // A group that failed is sent again next time.
+            let _ = self.renders.render(group);
+        }
+    }
+}
+
+impl Drop for DebugDrawFlusher {
+    fn drop(&mut self) {
+        if self.active {
+            FLUSHER_ACTIVE.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+/// Picks a render group id for a category. Ids stay below
+/// [`RenderManager::ID_STRIDE`] so that categories which need to be split
+/// across several groups don't run into each other.
+pub(crate) fn group_id(owner: i32, category: &str) -> i32 {
+    // FNV-1a, which unlike `DefaultHasher` is the same across runs.
+    let mut hash = 0x811c_9dc5_u32;
+    let owner = owner.to_le_bytes();
+    for &byte in owner.iter().chain(category.as_bytes()) {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x0100_0193);
+    }
+    (hash % RenderManager::ID_STRIDE as u32) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        debug_draw::{group_id, DebugDrawFlusher},
+        RenderManager,
+    };
+
+    #[test]
+    fn group_ids() {
+        let id = group_id(0, "path");
+        assert!((0..RenderManager::ID_STRIDE).contains(&id));
+        // Stable across runs, and different for each bot.
+        assert_eq!(group_id(0, "path"), id);
+        assert_ne!(group_id(1, "path"), id);
+        assert_ne!(group_id(0, "targets"), id);
+    }
+
+    #[test]
+    fn one_flusher_at_a_time() {
+        let first = DebugDrawFlusher::new(0);
+        let second = DebugDrawFlusher::new(1);
+        assert!(first.active);
+        assert!(!second.active);
+        drop(first);
+        assert!(DebugDrawFlusher::new(2).active);
+    }
+}
@@ -1,8 +1,9 @@
-use crate::{ffi::*, utils::maybe_join};
+use crate::{backend::CoreBackend, error::Error, ffi::*, utils::maybe_join};
 use libloading::Library;
 use std::{
-    io,
+    os::raw::{c_int, c_void},
     path::Path,
+    ptr::null_mut,
     sync::atomic::{AtomicBool, Ordering},
 };
 
@@ -38,6 +39,11 @@ type SendChat = extern "C" fn(
     callback: CallbackFunction,
     pID: *mut ::std::os::raw::c_uint,
 ) -> RLBotCoreStatus;
+type ReceiveChat = extern "C" fn(
+    botIndex: ::std::os::raw::c_int,
+    teamIndex: ::std::os::raw::c_int,
+    lastMessageIndex: ::std::os::raw::c_int,
+) -> ByteBuffer;
 type UpdatePlayerInput =
     extern "C" fn(playerInput: PlayerInput, playerIndex: ::std::os::raw::c_int) -> RLBotCoreStatus;
 type UpdatePlayerInputFlatbuffer = extern "C" fn(
@@ -70,6 +76,8 @@ pub struct RLBotCoreInterface {
     /// FlatBuffer version of send_chat
     pub send_quick_chat: SendQuickChat,
     pub send_chat: SendChat,
+    /// Older versions of the DLL cannot receive chats.
+    receive_chat_raw: Option<ReceiveChat>,
     pub update_player_input: UpdatePlayerInput,
     pub update_player_input_flatbuffer: UpdatePlayerInputFlatbuffer,
     pub render_group: RenderGroup,
@@ -90,23 +98,7 @@ impl RLBotCoreInterface {
         ret
     }
 
-    pub fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
-        self.copy_and_free_byte_buffer(|| (self.update_field_info_flatbuffer_raw)())
-    }
-
-    pub fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
-        self.copy_and_free_byte_buffer(|| (self.update_live_data_packet_flatbuffer_raw)())
-    }
-
-    pub fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
-        self.copy_and_free_byte_buffer(|| (self.update_rigid_body_tick_flatbuffer_raw)())
-    }
-
-    pub fn get_ball_prediction(&self) -> Option<Vec<u8>> {
-        self.copy_and_free_byte_buffer(|| (self.get_ball_prediction_raw)())
-    }
-
-    pub fn load(rlbot_dll_directory: Option<&Path>) -> io::Result<RLBotCoreInterface> {
+    pub fn load(rlbot_dll_directory: Option<&Path>) -> Result<RLBotCoreInterface, Error> {
         if INITIALIZED.swap(true, Ordering::SeqCst) {
             panic!("RLBot can only be initialized once");
         }
@@ -114,7 +106,8 @@ impl RLBotCoreInterface {
         let path = maybe_join(rlbot_dll_directory, "RLBot_Core_Interface.dll");
         let library = Library::new(&path)
             .or_else(|_| Library::new(path.with_file_name("libRLBotInterface.so")))
-            .or_else(|_| Library::new(path.with_file_name("RLBot_Core_Interface_32.dll")))?;
+            .or_else(|_| Library::new(path.with_file_name("RLBot_Core_Interface_32.dll")))
+            .map_err(Error::LoadLibrary)?;
 
         // This DLL does not seem to clean itself up all the way when unloaded, so to
         // avoid segfaults/etc we need to make sure it stays loaded until the process
@@ -123,27 +116,149 @@ impl RLBotCoreInterface {
 
         unsafe {
             Ok(RLBotCoreInterface {
-                update_field_info_flatbuffer_raw: *library.get(b"UpdateFieldInfoFlatbuffer")?,
-                update_field_info: *library.get(b"UpdateFieldInfo")?,
-                update_live_data_packet_flatbuffer_raw: *library
-                    .get(b"UpdateLiveDataPacketFlatbuffer")?,
-                update_live_data_packet: *library.get(b"UpdateLiveDataPacket")?,
-                update_rigid_body_tick_flatbuffer_raw: *library
-                    .get(b"UpdateRigidBodyTickFlatbuffer")?,
-                update_rigid_body_tick: *library.get(b"UpdateRigidBodyTick")?,
-                free: *library.get(b"Free")?,
-                set_game_state: *library.get(b"SetGameState")?,
-                start_match: *library.get(b"StartMatch")?,
-                start_match_flatbuffer: *library.get(b"StartMatchFlatbuffer")?,
-                send_quick_chat: *library.get(b"SendQuickChat")?,
-                send_chat: *library.get(b"SendChat")?,
-                update_player_input: *library.get(b"UpdatePlayerInput")?,
-                update_player_input_flatbuffer: *library.get(b"UpdatePlayerInputFlatbuffer")?,
-                render_group: *library.get(b"RenderGroup")?,
-                is_initialized: *library.get(b"IsInitialized")?,
-                get_ball_prediction_raw: *library.get(b"GetBallPrediction")?,
-                get_ball_prediction_struct: *library.get(b"GetBallPredictionStruct")?,
+                update_field_info_flatbuffer_raw: symbol(library, "UpdateFieldInfoFlatbuffer")?,
+                update_field_info: symbol(library, "UpdateFieldInfo")?,
+                update_live_data_packet_flatbuffer_raw: symbol(
+                    library,
+                    "UpdateLiveDataPacketFlatbuffer",
+                )?,
+                update_live_data_packet: symbol(library, "UpdateLiveDataPacket")?,
+                update_rigid_body_tick_flatbuffer_raw: symbol(
+                    library,
+                    "UpdateRigidBodyTickFlatbuffer",
+                )?,
+                update_rigid_body_tick: symbol(library, "UpdateRigidBodyTick")?,
+                free: symbol(library, "Free")?,
+                set_game_state: symbol(library, "SetGameState")?,
+                start_match: symbol(library, "StartMatch")?,
+                start_match_flatbuffer: symbol(library, "StartMatchFlatbuffer")?,
+                send_quick_chat: symbol(library, "SendQuickChat")?,
+                send_chat: symbol(library, "SendChat")?,
+                receive_chat_raw: symbol(library, "ReceiveChat").ok(),
+                update_player_input: symbol(library, "UpdatePlayerInput")?,
+                update_player_input_flatbuffer: symbol(library, "UpdatePlayerInputFlatbuffer")?,
+                render_group: symbol(library, "RenderGroup")?,
+                is_initialized: symbol(library, "IsInitialized")?,
+                get_ball_prediction_raw: symbol(library, "GetBallPrediction")?,
+                get_ball_prediction_struct: symbol(library, "GetBallPredictionStruct")?,
             })
         }
     }
 }
+
+/// Looks up a function in the DLL.
+unsafe fn symbol<T: Copy>(library: &Library, name: &'static str) -> Result<T, Error> {
+    library
+        .get::<T>(name.as_bytes())
+        .map(|symbol| *symbol)
+        .map_err(|source| Error::MissingSymbol { name, source })
+}
+
+impl CoreBackend for RLBotCoreInterface {
+    fn is_initialized(&self) -> bool {
+        (self.is_initialized)()
+    }
+
+    fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.copy_and_free_byte_buffer(|| (self.update_field_info_flatbuffer_raw)())
+    }
+
+    fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.copy_and_free_byte_buffer(|| (self.update_live_data_packet_flatbuffer_raw)())
+    }
+
+    fn update_live_data_packet_flatbuffer_into(&self, buf: &mut Vec<u8>) -> bool {
+        let byte_buffer = (self.update_live_data_packet_flatbuffer_raw)();
+        let copied = byte_buffer.copy_into(buf);
+        (self.free)(byte_buffer.ptr);
+
+        copied
+    }
+
+    fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.copy_and_free_byte_buffer(|| (self.update_rigid_body_tick_flatbuffer_raw)())
+    }
+
+    fn get_ball_prediction(&self) -> Option<Vec<u8>> {
+        self.copy_and_free_byte_buffer(|| (self.get_ball_prediction_raw)())
+    }
+
+    fn set_game_state(&self, desired_game_state_buffer: &[u8]) -> RLBotCoreStatus {
+        (self.set_game_state)(
+            desired_game_state_buffer.as_ptr() as *mut c_void,
+            desired_game_state_buffer.len() as c_int,
+        )
+    }
+
+    fn start_match_flatbuffer(&self, match_settings_buffer: &[u8]) -> RLBotCoreStatus {
+        (self.start_match_flatbuffer)(
+            match_settings_buffer.as_ptr() as *mut c_void,
+            match_settings_buffer.len() as c_int,
+        )
+    }
+
+    fn send_quick_chat(&self, quick_chat_buffer: &[u8]) -> RLBotCoreStatus {
+        (self.send_quick_chat)(
+            quick_chat_buffer.as_ptr() as *mut c_void,
+            quick_chat_buffer.len() as c_int,
+        )
+    }
+
+    fn receive_chat(
+        &self,
+        player_index: i32,
+        team: i32,
+        last_message_index: i32,
+    ) -> Option<Vec<u8>> {
+        // Without the function, act as if nobody ever chats, like the default.
+        let receive_chat = self.receive_chat_raw?;
+        self.copy_and_free_byte_buffer(|| receive_chat(player_index, team, last_message_index))
+    }
+
+    fn update_player_input_flatbuffer(&self, player_input_buffer: &[u8]) -> RLBotCoreStatus {
+        (self.update_player_input_flatbuffer)(
+            player_input_buffer.as_ptr() as *mut c_void,
+            player_input_buffer.len() as c_int,
+        )
+    }
+
+    fn render_group(&self, render_group_buffer: &[u8]) -> RLBotCoreStatus {
+        (self.render_group)(
+            render_group_buffer.as_ptr() as *mut c_void,
+            render_group_buffer.len() as c_int,
+        )
+    }
+
+    fn update_field_info(&self, field_info: &mut FieldInfo) -> RLBotCoreStatus {
+        (self.update_field_info)(field_info)
+    }
+
+    fn update_live_data_packet(&self, packet: &mut LiveDataPacket) -> RLBotCoreStatus {
+        (self.update_live_data_packet)(packet)
+    }
+
+    fn update_rigid_body_tick(&self, tick: &mut RigidBodyTick) -> RLBotCoreStatus {
+        (self.update_rigid_body_tick)(tick)
+    }
+
+    fn start_match(&self, match_settings: MatchSettings) -> RLBotCoreStatus {
+        (self.start_match)(match_settings, None, null_mut())
+    }
+
+    fn send_chat(
+        &self,
+        quick_chat_preset: QuickChatPreset,
+        player_index: i32,
+        team_only: bool,
+    ) -> RLBotCoreStatus {
+        (self.send_chat)(quick_chat_preset, player_index, team_only, None, null_mut())
+    }
+
+    fn update_player_input(&self, player_input: PlayerInput, player_index: i32) -> RLBotCoreStatus {
+        (self.update_player_input)(player_input, player_index)
+    }
+
+    fn get_ball_prediction_struct(&self, result: &mut BallPredictionPacket) -> RLBotCoreStatus {
+        (self.get_ball_prediction_struct)(result)
+    }
+}
@@ -1,16 +1,394 @@
 use crate::ffi::RLBotCoreStatus;
-use std::{error::Error, fmt};
+use std::{error::Error as StdError, fmt, io, num::ParseIntError, path::PathBuf, time::Duration};
+
+/// Any error that can be returned by this crate.
+///
+/// More variants may be added in the future, so matches should always include
+/// a wildcard arm.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The RLBot interface DLL could not be found or loaded.
+    LoadLibrary(io::Error),
+    /// The RLBot interface DLL was loaded, but one of the functions we need
+    /// was missing from it. This usually means the DLL is from an
+    /// incompatible version of RLBot.
+    MissingSymbol {
+        /// The name of the missing function.
+        name: &'static str,
+        /// The underlying error from the dynamic loader.
+        source: io::Error,
+    },
+    /// RLBot was loaded, but did not report that it was initialized before
+    /// the timeout elapsed.
+    InitializationTimeout(TimeoutError),
+    /// No new packet or physics tick was received before the timeout
+    /// elapsed.
+    Timeout(TimeoutError),
+    /// RLBot returned an error code.
+    CoreStatus(RLBotError),
+    /// A flatbuffer received from RLBot could not be decoded.
+    InvalidFlatbuffer(InvalidFlatbufferError),
+    /// The command line looked like it came from the RLBot framework, but
+    /// could not be parsed.
+    InvalidFrameworkArgs(FrameworkArgsError),
+    /// The app was not launched by the RLBot framework.
+    NotLaunchedByFramework,
+    /// An I/O error, e.g. while creating a recording.
+    Io(io::Error),
+    /// A quick chat was dropped because the player has sent too many recently.
+    QuickChatRateLimited(RateLimitError),
+    /// In lockstep mode, a bot took longer than its deadline to respond to a
+    /// packet.
+    TimingViolation(TimingViolation),
+    /// A match configuration file could not be loaded.
+    Config(ConfigError),
+}
+
+impl StdError for Error {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            Error::LoadLibrary(source) => Some(source),
+            Error::MissingSymbol { source, .. } => Some(source),
+            Error::InitializationTimeout(source) => Some(source),
+            Error::Timeout(source) => Some(source),
+            Error::CoreStatus(source) => Some(source),
+            Error::InvalidFlatbuffer(source) => Some(source),
+            Error::InvalidFrameworkArgs(source) => Some(source),
+            Error::NotLaunchedByFramework => None,
+            Error::Io(source) => Some(source),
+            Error::QuickChatRateLimited(source) => Some(source),
+            Error::TimingViolation(source) => Some(source),
+            Error::Config(source) => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::LoadLibrary(_) => write!(f, "could not load the RLBot interface DLL"),
+            Error::MissingSymbol { name, .. } => {
+                write!(f, "the RLBot interface DLL has no function named {}", name)
+            }
+            Error::InitializationTimeout(_) => write!(f, "RLBot did not become initialized"),
+            Error::Timeout(_) => write!(f, "timed out waiting for RLBot"),
+            Error::CoreStatus(_) => write!(f, "RLBot returned an error"),
+            Error::InvalidFlatbuffer(_) => write!(f, "RLBot sent an invalid flatbuffer"),
+            Error::InvalidFrameworkArgs(_) => write!(f, "could not parse framework arguments"),
+            Error::NotLaunchedByFramework => write!(f, "not launched by framework"),
+            Error::Io(_) => write!(f, "I/O error"),
+            Error::QuickChatRateLimited(_) => write!(f, "quick chat was dropped"),
+            Error::TimingViolation(_) => write!(f, "bot missed its tick deadline"),
+            Error::Config(_) => write!(f, "could not load match configuration"),
+        }
+    }
+}
+
+impl From<RLBotError> for Error {
+    fn from(error: RLBotError) -> Self {
+        Error::CoreStatus(error)
+    }
+}
+
+impl From<InvalidFlatbufferError> for Error {
+    fn from(error: InvalidFlatbufferError) -> Self {
+        Error::InvalidFlatbuffer(error)
+    }
+}
+
+impl From<FrameworkArgsError> for Error {
+    fn from(error: FrameworkArgsError) -> Self {
+        Error::InvalidFrameworkArgs(error)
+    }
+}
+
+impl From<ConfigError> for Error {
+    fn from(error: ConfigError) -> Self {
+        Error::Config(error)
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(error: io::Error) -> Self {
+        Error::Io(error)
+    }
+}
 
 /// An error code from the RLBot interface.
 #[derive(Debug)]
 pub struct RLBotError {
+    /// The status returned by RLBot.
     pub status: RLBotCoreStatus,
 }
 
-impl Error for RLBotError {}
+impl StdError for RLBotError {}
 
 impl fmt::Display for RLBotError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "RLBotError({:?})", self.status)
     }
 }
+
+/// No new data was received from RLBot before the timeout elapsed. This
+/// usually means the game froze or crashed.
+#[derive(Debug)]
+pub struct TimeoutError {
+    /// How long we waited.
+    pub timeout: Duration,
+}
+
+impl StdError for TimeoutError {}
+
+impl fmt::Display for TimeoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "no new data received from RLBot within {:?}",
+            self.timeout
+        )
+    }
+}
+
+/// A message was dropped because too many were sent in a short time.
+#[derive(Debug)]
+pub struct RateLimitError {
+    /// How long until another message will be accepted.
+    pub retry_after: Duration,
+}
+
+impl StdError for RateLimitError {}
+
+impl fmt::Display for RateLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rate limited; try again in {:?}", self.retry_after)
+    }
+}
+
+/// A bot took longer than its deadline to respond to a packet.
+#[derive(Debug)]
+pub struct TimingViolation {
+    /// The `seconds_elapsed` of the packet that was answered late.
+    pub game_seconds: f32,
+    /// How long the bot was allowed to take.
+    pub deadline: Duration,
+    /// How long the bot actually took, from receiving the packet to sending
+    /// its input.
+    pub elapsed: Duration,
+}
+
+impl StdError for TimingViolation {}
+
+impl fmt::Display for TimingViolation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "tick at {}s took {:?}, but the deadline is {:?}",
+            self.game_seconds, self.elapsed, self.deadline,
+        )
+    }
+}
+
+/// A flatbuffer could not be decoded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum InvalidFlatbufferError {
+    /// The buffer is truncated, or otherwise does not hold a valid flatbuffer.
+    Malformed {
+        /// The type of the buffer's root table.
+        root_type: &'static str,
+        /// The position in the buffer where the problem was found.
+        offset: usize,
+    },
+    /// A field that we rely on was absent.
+    MissingField {
+        /// The table with the missing field.
+        table: &'static str,
+        /// The name of the missing field, as written in the schema.
+        field: &'static str,
+    },
+}
+
+impl StdError for InvalidFlatbufferError {}
+
+impl fmt::Display for InvalidFlatbufferError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidFlatbufferError::Malformed { root_type, offset } => write!(
+                f,
+                "malformed {} flatbuffer (at offset {})",
+                root_type, offset,
+            ),
+            InvalidFlatbufferError::MissingField { table, field } => {
+                write!(f, "missing required field {}.{}", table, field)
+            }
+        }
+    }
+}
+
+/// The command line looked like it came from the RLBot framework, but could
+/// not be parsed.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum FrameworkArgsError {
+    /// An argument that the framework always passes was missing, or out of
+    /// order.
+    MissingArgument {
+        /// The argument, e.g. `--player-index`.
+        argument: &'static str,
+    },
+    /// An argument's value could not be parsed.
+    InvalidValue {
+        /// The argument, e.g. `--player-index`.
+        argument: &'static str,
+        /// The value that was passed.
+        value: String,
+        /// The underlying error.
+        source: ParseIntError,
+    },
+}
+
+impl StdError for FrameworkArgsError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            FrameworkArgsError::MissingArgument { .. } => None,
+            FrameworkArgsError::InvalidValue { source, .. } => Some(source),
+        }
+    }
+}
+
+impl fmt::Display for FrameworkArgsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FrameworkArgsError::MissingArgument { argument } => {
+                write!(f, "missing argument {}", argument)
+            }
+            FrameworkArgsError::InvalidValue {
+                argument, value, ..
+            } => write!(f, "invalid value {:?} for {}", value, argument),
+        }
+    }
+}
+
+/// A match configuration file could not be loaded.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ConfigError {
+    /// A file could not be read.
+    Io {
+        /// The file.
+        path: PathBuf,
+        /// The underlying error.
+        source: io::Error,
+    },
+    /// A line is neither a section header, a `key = value` pair, a comment
+    /// nor blank.
+    Syntax {
+        /// The file.
+        path: PathBuf,
+        /// The line number, starting from 1.
+        line: usize,
+    },
+    /// A key is not one that we understand.
+    UnknownKey {
+        /// The file.
+        path: PathBuf,
+        /// The line number, starting from 1.
+        line: usize,
+        /// The key, lowercased.
+        key: String,
+    },
+    /// A value could not be parsed.
+    InvalidValue {
+        /// The file.
+        path: PathBuf,
+        /// The line number, starting from 1.
+        line: usize,
+        /// The key, lowercased.
+        key: String,
+        /// The value.
+        value: String,
+    },
+    /// A required key was absent.
+    MissingKey {
+        /// The file.
+        path: PathBuf,
+        /// The section the key belongs in.
+        section: String,
+        /// The key.
+        key: String,
+    },
+}
+
+impl StdError for ConfigError {
+    fn source(&self) -> Option<&(dyn StdError + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io { path, .. } => write!(f, "could not read {}", path.display()),
+            ConfigError::Syntax { path, line } => {
+                write!(f, "{}:{}: syntax error", path.display(), line)
+            }
+            ConfigError::UnknownKey { path, line, key } => {
+                write!(f, "{}:{}: unknown key {:?}", path.display(), line, key)
+            }
+            ConfigError::InvalidValue {
+                path,
+                line,
+                key,
+                value,
+            } => write!(
+                f,
+                "{}:{}: invalid value {:?} for {:?}",
+                path.display(),
+                line,
+                value,
+                key,
+            ),
+            ConfigError::MissingKey { path, section, key } => write!(
+                f,
+                "{}: missing key {:?} in [{}]",
+                path.display(),
+                key,
+                section,
+            ),
+        }
+    }
+}
+
+/// A string could not be parsed as a [`Color`](crate::Color).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ParseColorError;
+
+impl StdError for ParseColorError {}
+
+impl fmt::Display for ParseColorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "expected a color in the form #rrggbb or #aarrggbb")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::{Error, RLBotError},
+        ffi::RLBotCoreStatus,
+    };
+    use std::error::Error as StdError;
+
+    #[test]
+    fn source_chain_is_preserved() {
+        let error = Error::from(RLBotError {
+            status: RLBotCoreStatus::InvalidPlayerIndex,
+        });
+        let source = error.source().unwrap();
+        assert_eq!(source.to_string(), "RLBotError(InvalidPlayerIndex)");
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{ffi, ffi::ByteBuffer};
-use std::ptr;
+use std::{ptr, slice};
 
 impl ffi::LiveDataPacket {
     /// Yields the [`PlayerInfo`](ffi::PlayerInfo) for each player in the match.
@@ -77,6 +77,21 @@ impl ffi::PlayerConfiguration {
     }
 }
 
+impl ByteBuffer {
+    /// Copies the contents into `buf`, reusing its allocation. Returns false if
+    /// the buffer is empty.
+    pub(crate) fn copy_into(&self, buf: &mut Vec<u8>) -> bool {
+        let len = self.size as usize;
+        if len == 0 || self.ptr.is_null() {
+            return false;
+        }
+
+        buf.clear();
+        buf.extend_from_slice(unsafe { slice::from_raw_parts(self.ptr as *const u8, len) });
+        true
+    }
+}
+
 impl From<ByteBuffer> for Option<Vec<u8>> {
     fn from(byte_buffer: ByteBuffer) -> Self {
         let len = byte_buffer.size as usize;
@@ -1,10 +1,19 @@
 //! This module contains code for interoperating with RLBot's BotManager.
 
 use crate::{
+    debug_draw::DebugDrawFlusher,
+    error::{Error, FrameworkArgsError, TimingViolation},
     game::{ControllerState, GameTickPacket},
-    init_with_options, InitOptions,
+    init_with_options,
+    packeteer::{Packeteer, PacketeerOptions},
+    rlbot::RLBot,
+    InitOptions,
+};
+use std::{
+    env,
+    path::PathBuf,
+    time::{Duration, Instant},
 };
-use std::{env, error::Error, path::PathBuf};
 
 /// A bot that can run within the RLBot framework. Instances of `Bot` are used
 /// by the [`run_bot`] function.
@@ -26,6 +35,116 @@ pub trait Bot {
     /// controlled according to the [`PlayerInput`](ffi::PlayerInput) you
     /// return.
     fn tick(&mut self, packet: &GameTickPacket) -> ControllerState;
+
+    /// This is called in lockstep mode, after the input for a packet was sent
+    /// later than the [deadline](RunOptions::tick_deadline).
+    ///
+    /// By default, this prints the violation and carries on. To stop the game
+    /// loop instead, return [`Error::TimingViolation`].
+    fn timing_violation(&mut self, violation: TimingViolation) -> Result<(), Error> {
+        println!("{}", violation);
+        Ok(())
+    }
+}
+
+/// Options for the game loop in [`run_bot_with`] and
+/// [`run_hive_with`](crate::run_hive_with).
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # struct MyBot;
+/// # impl rlbot::Bot for MyBot {
+/// #     fn set_player_index(&mut self, index: usize) { unimplemented!() }
+/// #     fn tick(&mut self, packet: &rlbot::GameTickPacket) -> rlbot::ControllerState { unimplemented!() }
+/// # }
+/// let options = rlbot::RunOptions::new()
+///     .lockstep(true)
+///     .tick_deadline(Duration::from_millis(5));
+/// rlbot::run_bot_with(MyBot, options);
+/// ```
+#[derive(Clone)]
+pub struct RunOptions {
+    pub(crate) lockstep: bool,
+    tick_deadline: Duration,
+    packeteer_options: PacketeerOptions,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            lockstep: false,
+            // One physics tick.
+            tick_deadline: Duration::from_micros(8333),
+            packeteer_options: PacketeerOptions::default(),
+        }
+    }
+}
+
+impl RunOptions {
+    /// Constructs a new `RunOptions` with the default settings: lockstep off,
+    /// and a deadline of one physics tick (1/120th of a second).
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables or disables lockstep mode. This should match
+    /// [`MatchSettings::enable_lockstep`](crate::MatchSettings::enable_lockstep).
+    ///
+    /// In lockstep mode, the game waits for every bot's input before it
+    /// advances to the next frame. The loop polls for packets without sleeping
+    /// in between, so that it picks up each one as soon as possible, and
+    /// checks each answer against the [deadline](RunOptions::tick_deadline).
+    ///
+    /// Packets are still detected by polling, so if the game moves on without
+    /// waiting, e.g. because lockstep is not enabled in the match settings,
+    /// packets can still be missed.
+    pub fn lockstep(mut self, lockstep: bool) -> Self {
+        self.lockstep = lockstep;
+        self
+    }
+
+    /// Sets how long the bot may take to answer each packet in lockstep mode,
+    /// from fetching the packet to sending its input.
+    pub fn tick_deadline(mut self, tick_deadline: Duration) -> Self {
+        self.tick_deadline = tick_deadline;
+        self
+    }
+
+    /// Sets how the loop waits for packets.
+    pub fn packeteer_options(mut self, packeteer_options: PacketeerOptions) -> Self {
+        self.packeteer_options = packeteer_options;
+        self
+    }
+
+    /// Returns a `Packeteer` which waits for packets the way these options
+    /// ask for.
+    pub(crate) fn packeteer<'a>(&self, rlbot: &'a RLBot) -> Packeteer<'a> {
+        let options = self.packeteer_options.clone().busy_wait(self.lockstep);
+        rlbot.packeteer_with(options)
+    }
+
+    /// Returns the violation, if a packet fetched at `started` was answered
+    /// too late.
+    pub(crate) fn check_deadline(
+        &self,
+        packet: &GameTickPacket,
+        started: Instant,
+    ) -> Option<TimingViolation> {
+        if !self.lockstep {
+            return None;
+        }
+        let elapsed = started.elapsed();
+        if elapsed <= self.tick_deadline {
+            return None;
+        }
+        Some(TimingViolation {
+            game_seconds: packet.game_info.seconds_elapsed,
+            deadline: self.tick_deadline,
+            elapsed,
+        })
+    }
 }
 
 /// Runs a bot under control of the RLBot framework.
@@ -55,23 +174,47 @@ pub trait Bot {
 /// See [`examples/bot`] for a complete example.
 ///
 /// [`examples/bot`]: https://github.com/whatisaphone/rlbot-rust/blob/master/examples/bot/main.rs
-#[allow(deprecated)]
-pub fn run_bot<B: Bot>(mut bot: B) -> Result<(), Box<dyn Error>> {
-    let args = parse_framework_args()
-        .map_err(|_| Box::<dyn Error>::from("could not parse framework arguments"))?
-        .ok_or_else(|| Box::<dyn Error>::from("not launched by framework"))?;
+pub fn run_bot<B: Bot>(bot: B) -> Result<(), Error> {
+    run_bot_with(bot, RunOptions::default())
+}
+
+/// Like [`run_bot`], but runs the game loop according to the given
+/// [`RunOptions`].
+pub fn run_bot_with<B: Bot>(mut bot: B, options: RunOptions) -> Result<(), Error> {
+    let args = parse_framework_args()?.ok_or(Error::NotLaunchedByFramework)?;
 
     let player_index = args.player_index;
 
     let rlbot = init_with_options(args.into())?;
 
+    run_bot_loop(&rlbot, player_index, &mut bot, &options)
+}
+
+/// Runs the game loop for a bot which controls the given player, using an
+/// `RLBot` which has already been initialized.
+///
+/// This is what [`run_bot_with`] does after connecting to the framework. It
+/// can also be used to drive a bot without the framework, e.g. with a
+/// [`MockBackend`](crate::MockBackend). It only returns on error.
+pub fn run_bot_loop<B: Bot>(
+    rlbot: &RLBot,
+    player_index: i32,
+    bot: &mut B,
+    options: &RunOptions,
+) -> Result<(), Error> {
     bot.set_player_index(player_index as usize);
 
-    let mut packets = rlbot.packeteer();
+    let mut packets = options.packeteer(rlbot);
+    let mut debug_draw = DebugDrawFlusher::new(player_index);
     loop {
         let packet = packets.next()?;
+        let started = packets.fetched();
         let input = bot.tick(&packet);
         rlbot.update_player_input(player_index, &input)?;
+        if let Some(violation) = options.check_deadline(&packet, started) {
+            bot.timing_violation(violation)?;
+        }
+        debug_draw.flush(rlbot);
     }
 }
 
@@ -83,13 +226,13 @@ pub fn run_bot<B: Bot>(mut bot: B) -> Result<(), Box<dyn Error>> {
 /// * `Ok(None)` – if the app was *not* launched by the framework.
 /// * `Err(_)` – if it appears the app was launched by the framework, but we
 ///   could not understand the arguments.
-pub fn parse_framework_args() -> Result<Option<FrameworkArgs>, ()> {
+pub fn parse_framework_args() -> Result<Option<FrameworkArgs>, FrameworkArgsError> {
     parse_framework_command_line(env::args().skip(1))
 }
 
 fn parse_framework_command_line(
     mut args: impl Iterator<Item = String>,
-) -> Result<Option<FrameworkArgs>, ()> {
+) -> Result<Option<FrameworkArgs>, FrameworkArgsError> {
     // Currently this only needs to interoperate with one caller – RLBot Python's
     // BaseSubprocessAgent. No public interface has been committed to, so we can
     // afford to be rigid and inflexible with the parsing.
@@ -100,10 +243,9 @@ fn parse_framework_command_line(
             return Ok(None);
         };
 
-    if args.next().as_ref().map(|s| &s[..]) != Some("--player-index") {
-        return Err(());
-    }
-    let player_index = args.next().ok_or(())?.parse().map_err(|_| ())?;
+    expect_argument(&mut args, "--player-index")?;
+    let player_index = expect_value(&mut args, "--player-index")?;
+    let player_index = parse_value("--player-index", &player_index)?;
 
     Ok(Some(FrameworkArgs {
         rlbot_version,
@@ -116,20 +258,49 @@ fn parse_framework_command_line(
 /// Parse the version and dll directory arguments passed by the framework.
 pub fn parse_version_and_directory(
     mut args: impl Iterator<Item = String>,
-) -> Result<Option<(String, PathBuf)>, ()> {
+) -> Result<Option<(String, PathBuf)>, FrameworkArgsError> {
     if args.next().as_ref().map(|s| &s[..]) != Some("--rlbot-version") {
         return Ok(None); // not launched by the framework
     }
-    let rlbot_version = args.next().ok_or(())?;
+    let rlbot_version = expect_value(&mut args, "--rlbot-version")?;
 
-    if args.next().as_ref().map(|s| &s[..]) != Some("--rlbot-dll-directory") {
-        return Err(());
-    }
-    let rlbot_dll_directory = PathBuf::from(args.next().ok_or(())?);
+    expect_argument(&mut args, "--rlbot-dll-directory")?;
+    let rlbot_dll_directory = PathBuf::from(expect_value(&mut args, "--rlbot-dll-directory")?);
 
     Ok(Some((rlbot_version, rlbot_dll_directory)))
 }
 
+/// Consumes the next argument, which must be the given flag.
+pub(crate) fn expect_argument(
+    mut args: impl Iterator<Item = String>,
+    argument: &'static str,
+) -> Result<(), FrameworkArgsError> {
+    if args.next().as_ref().map(|s| &s[..]) != Some(argument) {
+        return Err(FrameworkArgsError::MissingArgument { argument });
+    }
+    Ok(())
+}
+
+/// Consumes the value that follows a flag.
+pub(crate) fn expect_value(
+    mut args: impl Iterator<Item = String>,
+    argument: &'static str,
+) -> Result<String, FrameworkArgsError> {
+    args.next()
+        .ok_or(FrameworkArgsError::MissingArgument { argument })
+}
+
+/// Parses a number passed as the value of a flag.
+pub(crate) fn parse_value(argument: &'static str, value: &str) -> Result<i32, FrameworkArgsError> {
+    value
+        .parse()
+        .map_err(|source| FrameworkArgsError::InvalidValue {
+            argument,
+            value: value.to_owned(),
+            source,
+        })
+}
+
 /// The arguments passed by the RLBot framework.
 pub struct FrameworkArgs {
     /// The version of the RLBot framework used to launch the app. This is the
@@ -161,8 +332,9 @@ impl From<FrameworkArgs> for InitOptions {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::{error::Error as StdError, num::ParseIntError};
 
-    fn pfcl(ss: Vec<&str>) -> Result<Option<FrameworkArgs>, ()> {
+    fn pfcl(ss: Vec<&str>) -> Result<Option<FrameworkArgs>, FrameworkArgsError> {
         parse_framework_command_line(ss.into_iter().map(str::to_string))
     }
 
@@ -202,5 +374,34 @@ mod tests {
 
         let args = pfcl(vec!["--rlbot-version", "1.8.1"]);
         assert!(args.is_err());
+
+        let args = pfcl(vec![
+            "--rlbot-version",
+            "1.8.1",
+            "--rlbot-dll-directory",
+            "/tmp",
+        ]);
+        match args {
+            Err(FrameworkArgsError::MissingArgument { argument }) => {
+                assert_eq!(argument, "--player-index");
+            }
+            _ => panic!(),
+        }
+
+        let args = pfcl(vec![
+            "--rlbot-version",
+            "1.8.1",
+            "--rlbot-dll-directory",
+            "/tmp",
+            "--player-index",
+            "one",
+        ]);
+        let error = Error::from(args.err().unwrap());
+        let source = error.source().unwrap();
+        assert_eq!(
+            source.to_string(),
+            "invalid value \"one\" for --player-index"
+        );
+        assert!(source.source().unwrap().is::<ParseIntError>());
     }
 }
@@ -27,6 +27,7 @@ pub struct ControllerState {
     pub use_item: bool,
 }
 
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct Vector3 {
     pub x: f32,
     pub y: f32,
@@ -35,7 +36,9 @@ pub struct Vector3 {
 
 /// Expresses the rotation state of an object in Euler angles, with values in
 /// radians.
-#[derive(Default)]
+///
+/// See [`RotationMatrix`](crate::RotationMatrix) for the axis conventions.
+#[derive(Copy, Clone, Debug, Default, PartialEq)]
 pub struct Rotator {
     pub pitch: f32,
     pub yaw: f32,
@@ -45,6 +48,7 @@ pub struct Rotator {
 /// Expresses the rotation state of an object.
 /// Learn about quaternions here: https://en.wikipedia.org/wiki/Quaternions_and_spatial_rotation
 /// You can tinker with them here to build an intuition: https://quaternions.online/
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct Quaternion {
     pub x: f32,
     pub y: f32,
@@ -64,6 +68,8 @@ pub struct Touch {
     pub normal: Vector3,
     /// The Team which the touch belongs to, 0 for blue 1 for orange.
     pub team: i32,
+    /// The index of the player involved with the touch.
+    pub player_index: i32,
     pub(crate) _non_exhaustive: (),
 }
 
@@ -105,6 +111,23 @@ pub struct PlayerInfo {
     pub name: String,
     pub team: i32,
     pub boost: i32,
+    /// The dimensions of the car's hitbox. This may be `None` with older
+    /// versions of RLBot.
+    pub hitbox: Option<BoxShape>,
+    pub(crate) _non_exhaustive: (),
+}
+
+/// The dimensions of an axis-aligned box, e.g. a car's hitbox.
+///
+/// The box is centered on its owner's origin, with `length` along the local
+/// `x` (forward) axis, `width` along `y` (right), and `height` along `z` (up).
+/// This version of the RLBot schema does not report how far a car's hitbox is
+/// offset from its origin, so collision checks against it are approximate.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct BoxShape {
+    pub length: f32,
+    pub width: f32,
+    pub height: f32,
     pub(crate) _non_exhaustive: (),
 }
 
@@ -119,6 +142,38 @@ pub struct BallInfo {
     pub physics: Physics,
     pub latest_touch: Option<Touch>,
     pub dropshot_info: Option<DropshotBallInfo>,
+    /// The shape of the ball, which changes with the
+    /// [`BallTypeOption`](crate::BallTypeOption) mutator. This may be `None`
+    /// with older versions of RLBot.
+    pub shape: Option<CollisionShape>,
+    pub(crate) _non_exhaustive: (),
+}
+
+/// The shape of an object's collision volume.
+#[derive(Copy, Clone, Debug, PartialEq)]
+#[non_exhaustive]
+pub enum CollisionShape {
+    /// E.g. the cube ball.
+    Box(BoxShape),
+    /// E.g. the standard ball and the basketball.
+    Sphere(SphereShape),
+    /// E.g. the hockey puck.
+    Cylinder(CylinderShape),
+}
+
+/// The dimensions of a sphere.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SphereShape {
+    pub diameter: f32,
+    pub(crate) _non_exhaustive: (),
+}
+
+/// The dimensions of a cylinder standing upright along its owner's local `z`
+/// axis.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct CylinderShape {
+    pub diameter: f32,
+    pub height: f32,
     pub(crate) _non_exhaustive: (),
 }
 
@@ -243,6 +298,23 @@ pub struct BallPrediction {
     pub(crate) _non_exhaustive: (),
 }
 
+/// A quick chat which was sent by a player.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct QuickChat {
+    /// The index of the player who sent the chat.
+    pub player_index: i32,
+    /// What was said.
+    pub selection: flat::QuickChatSelection,
+    /// True if the chat was only shown to the sender's team.
+    pub team_only: bool,
+    /// A number assigned by RLBot, which increases with every chat sent during
+    /// the match.
+    pub message_index: i32,
+    /// Seconds that had elapsed in the game when the chat was sent.
+    pub game_seconds: f32,
+    pub(crate) _non_exhaustive: (),
+}
+
 pub(crate) fn build_update_player_input(
     player_index: i32,
     controller_state: &ControllerState,
@@ -1,4 +1,15 @@
-use crate::{flat, game::*, utils::flat_vector_iter};
+use crate::{error::InvalidFlatbufferError, flat, game::*, utils::flat_vector_iter};
+use std::convert::{TryFrom, TryInto};
+
+/// Unwraps a field which our types require, but which flatbuffers considers
+/// optional.
+fn required<T>(
+    value: Option<T>,
+    table: &'static str,
+    field: &'static str,
+) -> Result<T, InvalidFlatbufferError> {
+    value.ok_or(InvalidFlatbufferError::MissingField { table, field })
+}
 
 impl From<flat::ControllerState<'_>> for ControllerState {
     fn from(state: flat::ControllerState<'_>) -> Self {
@@ -16,42 +27,62 @@ impl From<flat::ControllerState<'_>> for ControllerState {
     }
 }
 
-impl From<flat::GameTickPacket<'_>> for GameTickPacket {
-    fn from(packet: flat::GameTickPacket<'_>) -> Self {
-        Self {
-            players: flat_vector_iter(packet.players().unwrap())
-                .map(PlayerInfo::from)
-                .collect(),
-            boost_pad_states: flat_vector_iter(packet.boostPadStates().unwrap())
-                .map(BoostPadState::from)
-                .collect(),
-            ball: packet.ball().map(BallInfo::from),
-            game_info: packet.gameInfo().unwrap().into(),
+impl TryFrom<flat::GameTickPacket<'_>> for GameTickPacket {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(packet: flat::GameTickPacket<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            players: flat_vector_iter(required(packet.players(), "GameTickPacket", "players")?)
+                .map(PlayerInfo::try_from)
+                .collect::<Result<_, _>>()?,
+            boost_pad_states: flat_vector_iter(required(
+                packet.boostPadStates(),
+                "GameTickPacket",
+                "boostPadStates",
+            )?)
+            .map(BoostPadState::from)
+            .collect(),
+            ball: packet.ball().map(BallInfo::try_from).transpose()?,
+            game_info: required(packet.gameInfo(), "GameTickPacket", "gameInfo")?.into(),
             tile_information: packet
                 .tileInformation()
                 .map(|ti| flat_vector_iter(ti).map(DropshotTile::from).collect()),
-            teams: flat_vector_iter(packet.teams().unwrap())
+            teams: flat_vector_iter(required(packet.teams(), "GameTickPacket", "teams")?)
                 .map(TeamInfo::from)
                 .collect(),
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::PlayerInfo<'_>> for PlayerInfo {
-    fn from(info: flat::PlayerInfo<'_>) -> Self {
-        Self {
-            physics: info.physics().unwrap().into(),
-            score_info: info.scoreInfo().unwrap().into(),
+impl TryFrom<flat::PlayerInfo<'_>> for PlayerInfo {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(info: flat::PlayerInfo<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            physics: required(info.physics(), "PlayerInfo", "physics")?.try_into()?,
+            score_info: required(info.scoreInfo(), "PlayerInfo", "scoreInfo")?.into(),
             is_demolished: info.isDemolished(),
             has_wheel_contact: info.hasWheelContact(),
             is_supersonic: info.isSupersonic(),
             is_bot: info.isBot(),
             jumped: info.jumped(),
             double_jumped: info.doubleJumped(),
-            name: info.name().unwrap().to_string(),
+            name: required(info.name(), "PlayerInfo", "name")?.to_string(),
             team: info.team(),
             boost: info.boost(),
+            hitbox: info.hitbox().map(BoxShape::from),
+            _non_exhaustive: (),
+        })
+    }
+}
+
+impl From<flat::BoxShape<'_>> for BoxShape {
+    fn from(shape: flat::BoxShape<'_>) -> Self {
+        Self {
+            length: shape.length(),
+            width: shape.width(),
+            height: shape.height(),
             _non_exhaustive: (),
         }
     }
@@ -67,29 +98,75 @@ impl From<flat::BoostPadState<'_>> for BoostPadState {
     }
 }
 
-impl From<flat::BallInfo<'_>> for BallInfo {
-    fn from(info: flat::BallInfo<'_>) -> Self {
-        Self {
-            physics: info.physics().unwrap().into(),
-            latest_touch: info.latestTouch().map(Touch::from),
+impl TryFrom<flat::BallInfo<'_>> for BallInfo {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(info: flat::BallInfo<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            physics: required(info.physics(), "BallInfo", "physics")?.try_into()?,
+            latest_touch: info.latestTouch().map(Touch::try_from).transpose()?,
             dropshot_info: info.dropShotInfo().map(DropshotBallInfo::from),
+            shape: collision_shape(info.shape_type(), info.shape()),
+            _non_exhaustive: (),
+        })
+    }
+}
+
+/// Decodes a `CollisionShape` union. Returns `None` if the shape is absent or
+/// of an unknown type.
+pub(crate) fn collision_shape(
+    shape_type: flat::CollisionShape,
+    shape: Option<flatbuffers::Table<'_>>,
+) -> Option<CollisionShape> {
+    let shape = shape?;
+    match shape_type {
+        flat::CollisionShape::BoxShape => Some(CollisionShape::Box(
+            flat::BoxShape::init_from_table(shape).into(),
+        )),
+        flat::CollisionShape::SphereShape => Some(CollisionShape::Sphere(
+            flat::SphereShape::init_from_table(shape).into(),
+        )),
+        flat::CollisionShape::CylinderShape => Some(CollisionShape::Cylinder(
+            flat::CylinderShape::init_from_table(shape).into(),
+        )),
+        flat::CollisionShape::NONE => None,
+    }
+}
+
+impl From<flat::SphereShape<'_>> for SphereShape {
+    fn from(shape: flat::SphereShape<'_>) -> Self {
+        Self {
+            diameter: shape.diameter(),
             _non_exhaustive: (),
         }
     }
 }
 
-impl From<flat::Physics<'_>> for Physics {
-    fn from(physics: flat::Physics<'_>) -> Self {
+impl From<flat::CylinderShape<'_>> for CylinderShape {
+    fn from(shape: flat::CylinderShape<'_>) -> Self {
         Self {
-            location: physics.location().unwrap().into(),
-            rotation: physics.rotation().map_or(Rotator::default(), Rotator::from),
-            velocity: physics.velocity().unwrap().into(),
-            angular_velocity: physics.angularVelocity().unwrap().into(),
+            diameter: shape.diameter(),
+            height: shape.height(),
             _non_exhaustive: (),
         }
     }
 }
 
+impl TryFrom<flat::Physics<'_>> for Physics {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(physics: flat::Physics<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            location: required(physics.location(), "Physics", "location")?.into(),
+            rotation: physics.rotation().map_or(Rotator::default(), Rotator::from),
+            velocity: required(physics.velocity(), "Physics", "velocity")?.into(),
+            angular_velocity: required(physics.angularVelocity(), "Physics", "angularVelocity")?
+                .into(),
+            _non_exhaustive: (),
+        })
+    }
+}
+
 impl From<&flat::Vector3> for Vector3 {
     fn from(vector3: &flat::Vector3) -> Self {
         Self {
@@ -121,16 +198,19 @@ impl From<&flat::Quaternion> for Quaternion {
     }
 }
 
-impl From<flat::Touch<'_>> for Touch {
-    fn from(touch: flat::Touch<'_>) -> Self {
-        Self {
-            player_name: touch.playerName().unwrap().to_string(),
+impl TryFrom<flat::Touch<'_>> for Touch {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(touch: flat::Touch<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            player_name: required(touch.playerName(), "Touch", "playerName")?.to_string(),
             game_seconds: touch.gameSeconds(),
-            location: touch.location().unwrap().into(),
-            normal: touch.normal().unwrap().into(),
+            location: required(touch.location(), "Touch", "location")?.into(),
+            normal: required(touch.normal(), "Touch", "normal")?.into(),
             team: touch.team(),
+            player_index: touch.playerIndex(),
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
@@ -196,102 +276,361 @@ impl From<flat::ScoreInfo<'_>> for ScoreInfo {
     }
 }
 
-impl From<flat::GoalInfo<'_>> for GoalInfo {
-    fn from(goal_info: flat::GoalInfo<'_>) -> Self {
-        Self {
+impl TryFrom<flat::GoalInfo<'_>> for GoalInfo {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(goal_info: flat::GoalInfo<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
             team_num: goal_info.teamNum(),
-            location: goal_info.location().unwrap().into(),
-            direction: goal_info.direction().unwrap().into(),
+            location: required(goal_info.location(), "GoalInfo", "location")?.into(),
+            direction: required(goal_info.direction(), "GoalInfo", "direction")?.into(),
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::BoostPad<'_>> for BoostPad {
-    fn from(boost_pad: flat::BoostPad<'_>) -> Self {
-        Self {
-            location: boost_pad.location().unwrap().into(),
+impl TryFrom<flat::BoostPad<'_>> for BoostPad {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(boost_pad: flat::BoostPad<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            location: required(boost_pad.location(), "BoostPad", "location")?.into(),
             full_boost: boost_pad.isFullBoost(),
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::FieldInfo<'_>> for FieldInfo {
-    fn from(info: flat::FieldInfo<'_>) -> Self {
-        Self {
-            boost_pads: flat_vector_iter(info.boostPads().unwrap())
-                .map(BoostPad::from)
-                .collect(),
-            goals: flat_vector_iter(info.goals().unwrap())
-                .map(GoalInfo::from)
-                .collect(),
+impl TryFrom<flat::FieldInfo<'_>> for FieldInfo {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(info: flat::FieldInfo<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            boost_pads: flat_vector_iter(required(info.boostPads(), "FieldInfo", "boostPads")?)
+                .map(BoostPad::try_from)
+                .collect::<Result<_, _>>()?,
+            goals: flat_vector_iter(required(info.goals(), "FieldInfo", "goals")?)
+                .map(GoalInfo::try_from)
+                .collect::<Result<_, _>>()?,
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::RigidBodyState<'_>> for RigidBodyState {
-    fn from(state: flat::RigidBodyState<'_>) -> Self {
-        Self {
+impl TryFrom<flat::RigidBodyState<'_>> for RigidBodyState {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(state: flat::RigidBodyState<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
             frame: state.frame(),
-            location: state.location().unwrap().into(),
-            rotation: state.rotation().unwrap().into(),
-            velocity: state.velocity().unwrap().into(),
-            angular_velocity: state.angularVelocity().unwrap().into(),
+            location: required(state.location(), "RigidBodyState", "location")?.into(),
+            rotation: required(state.rotation(), "RigidBodyState", "rotation")?.into(),
+            velocity: required(state.velocity(), "RigidBodyState", "velocity")?.into(),
+            angular_velocity: required(
+                state.angularVelocity(),
+                "RigidBodyState",
+                "angularVelocity",
+            )?
+            .into(),
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::PlayerRigidBodyState<'_>> for PlayerRigidBodyState {
-    fn from(state: flat::PlayerRigidBodyState<'_>) -> Self {
-        Self {
-            state: state.state().unwrap().into(),
-            input: state.input().unwrap().into(),
+impl TryFrom<flat::PlayerRigidBodyState<'_>> for PlayerRigidBodyState {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(state: flat::PlayerRigidBodyState<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            state: required(state.state(), "PlayerRigidBodyState", "state")?.try_into()?,
+            input: required(state.input(), "PlayerRigidBodyState", "input")?.into(),
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::BallRigidBodyState<'_>> for BallRigidBodyState {
-    fn from(state: flat::BallRigidBodyState<'_>) -> Self {
-        Self {
-            state: state.state().map(RigidBodyState::from),
+impl TryFrom<flat::BallRigidBodyState<'_>> for BallRigidBodyState {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(state: flat::BallRigidBodyState<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            state: state.state().map(RigidBodyState::try_from).transpose()?,
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::RigidBodyTick<'_>> for RigidBodyTick {
-    fn from(tick: flat::RigidBodyTick<'_>) -> Self {
-        Self {
-            ball: tick.ball().map(BallRigidBodyState::from),
-            players: flat_vector_iter(tick.players().unwrap())
-                .map(PlayerRigidBodyState::from)
-                .collect(),
+impl TryFrom<flat::RigidBodyTick<'_>> for RigidBodyTick {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(tick: flat::RigidBodyTick<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            ball: tick.ball().map(BallRigidBodyState::try_from).transpose()?,
+            players: flat_vector_iter(required(tick.players(), "RigidBodyTick", "players")?)
+                .map(PlayerRigidBodyState::try_from)
+                .collect::<Result<_, _>>()?,
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::PredictionSlice<'_>> for PredictionSlice {
-    fn from(slice: flat::PredictionSlice<'_>) -> Self {
-        Self {
+impl TryFrom<flat::PredictionSlice<'_>> for PredictionSlice {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(slice: flat::PredictionSlice<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
             game_seconds: slice.gameSeconds(),
-            physics: slice.physics().unwrap().into(),
+            physics: required(slice.physics(), "PredictionSlice", "physics")?.try_into()?,
             _non_exhaustive: (),
-        }
+        })
     }
 }
 
-impl From<flat::BallPrediction<'_>> for BallPrediction {
-    fn from(ball_prediction: flat::BallPrediction<'_>) -> Self {
+impl TryFrom<flat::BallPrediction<'_>> for BallPrediction {
+    type Error = InvalidFlatbufferError;
+
+    fn try_from(ball_prediction: flat::BallPrediction<'_>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            slices: flat_vector_iter(required(
+                ball_prediction.slices(),
+                "BallPrediction",
+                "slices",
+            )?)
+            .map(PredictionSlice::try_from)
+            .collect::<Result<_, _>>()?,
+            _non_exhaustive: (),
+        })
+    }
+}
+
+impl From<flat::QuickChat<'_>> for QuickChat {
+    fn from(chat: flat::QuickChat<'_>) -> Self {
         Self {
-            slices: flat_vector_iter(ball_prediction.slices().unwrap())
-                .map(PredictionSlice::from)
-                .collect(),
+            player_index: chat.playerIndex(),
+            selection: chat.quickChatSelection(),
+            team_only: chat.teamOnly(),
+            message_index: chat.messageIndex(),
+            game_seconds: chat.timeStamp(),
             _non_exhaustive: (),
         }
     }
 }
+
+/// Converts a [`flat::QuickChatMessages`]. A missing `messages` field means
+/// there were no messages.
+pub(crate) fn quick_chat_messages(messages: flat::QuickChatMessages<'_>) -> Vec<QuickChat> {
+    match messages.messages() {
+        Some(messages) => flat_vector_iter(messages).map(QuickChat::from).collect(),
+        None => Vec::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{flat, game::*};
+    use std::convert::TryFrom;
+
+    // The `*Args` structs below list every field, with no
+    // `..Default::default()`, and the results are destructured without `..`.
+    // If the schema gains a field and it is not mapped, these stop compiling.
+
+    #[test]
+    fn player_info_maps_every_field() {
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let zero = flat::Vector3::new(0.0, 0.0, 0.0);
+        let physics = flat::Physics::create(
+            &mut builder,
+            &flat::PhysicsArgs {
+                location: Some(&zero),
+                rotation: None,
+                velocity: Some(&zero),
+                angularVelocity: Some(&zero),
+            },
+        );
+        let score_info = flat::ScoreInfo::create(
+            &mut builder,
+            &flat::ScoreInfoArgs {
+                score: 1,
+                goals: 2,
+                ownGoals: 3,
+                assists: 4,
+                saves: 5,
+                shots: 6,
+                demolitions: 7,
+            },
+        );
+        let name = builder.create_string("Chell");
+        let hitbox = flat::BoxShape::create(
+            &mut builder,
+            &flat::BoxShapeArgs {
+                length: 118.0,
+                width: 84.2,
+                height: 36.2,
+            },
+        );
+        let info = flat::PlayerInfo::create(
+            &mut builder,
+            &flat::PlayerInfoArgs {
+                physics: Some(physics),
+                scoreInfo: Some(score_info),
+                isDemolished: true,
+                hasWheelContact: true,
+                isSupersonic: true,
+                isBot: true,
+                jumped: true,
+                doubleJumped: true,
+                name: Some(name),
+                team: 1,
+                boost: 33,
+                hitbox: Some(hitbox),
+            },
+        );
+        builder.finish(info, None);
+
+        let info = flatbuffers::get_root::<flat::PlayerInfo<'_>>(builder.finished_data());
+        let PlayerInfo {
+            physics: _,
+            score_info,
+            is_demolished,
+            has_wheel_contact,
+            is_supersonic,
+            is_bot,
+            jumped,
+            double_jumped,
+            name,
+            team,
+            boost,
+            hitbox,
+            _non_exhaustive,
+        } = PlayerInfo::try_from(info).unwrap();
+        let ScoreInfo {
+            score,
+            goals,
+            own_goals,
+            assists,
+            saves,
+            shots,
+            demolitions,
+            _non_exhaustive,
+        } = score_info;
+        assert_eq!(
+            (score, goals, own_goals, assists, saves, shots, demolitions),
+            (1, 2, 3, 4, 5, 6, 7),
+        );
+        assert!(is_demolished && has_wheel_contact && is_supersonic && is_bot);
+        assert!(jumped && double_jumped);
+        assert_eq!((name.as_str(), team, boost), ("Chell", 1, 33));
+        let BoxShape {
+            length,
+            width,
+            height,
+            _non_exhaustive,
+        } = hitbox.unwrap();
+        assert_eq!((length, width, height), (118.0, 84.2, 36.2));
+    }
+
+    #[test]
+    fn ball_info_maps_every_field() {
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let zero = flat::Vector3::new(0.0, 0.0, 0.0);
+        let physics = flat::Physics::create(
+            &mut builder,
+            &flat::PhysicsArgs {
+                location: Some(&zero),
+                rotation: None,
+                velocity: Some(&zero),
+                angularVelocity: Some(&zero),
+            },
+        );
+        let dropshot_info = flat::DropShotBallInfo::create(
+            &mut builder,
+            &flat::DropShotBallInfoArgs {
+                absorbedForce: 1.5,
+                damageIndex: 2,
+                forceAccumRecent: 3.5,
+            },
+        );
+        let shape = flat::CylinderShape::create(
+            &mut builder,
+            &flat::CylinderShapeArgs {
+                diameter: 163.0,
+                height: 33.0,
+            },
+        );
+        let info = flat::BallInfo::create(
+            &mut builder,
+            &flat::BallInfoArgs {
+                physics: Some(physics),
+                latestTouch: None,
+                dropShotInfo: Some(dropshot_info),
+                shape_type: flat::CollisionShape::CylinderShape,
+                shape: Some(shape.as_union_value()),
+            },
+        );
+        builder.finish(info, None);
+
+        let info = flatbuffers::get_root::<flat::BallInfo<'_>>(builder.finished_data());
+        let BallInfo {
+            physics: _,
+            latest_touch,
+            dropshot_info,
+            shape,
+            _non_exhaustive,
+        } = BallInfo::try_from(info).unwrap();
+        assert!(latest_touch.is_none());
+        let DropshotBallInfo {
+            absorbed_force,
+            damage_index,
+            force_accum_recent,
+            _non_exhaustive,
+        } = dropshot_info.unwrap();
+        assert_eq!(
+            (absorbed_force, damage_index, force_accum_recent),
+            (1.5, 2, 3.5),
+        );
+        match shape {
+            Some(CollisionShape::Cylinder(CylinderShape {
+                diameter,
+                height,
+                _non_exhaustive,
+            })) => assert_eq!((diameter, height), (163.0, 33.0)),
+            _ => panic!("expected a cylinder"),
+        }
+    }
+
+    #[test]
+    fn touch_maps_every_field() {
+        let mut builder = flatbuffers::FlatBufferBuilder::new();
+        let player_name = builder.create_string("Chell");
+        let location = flat::Vector3::new(1.0, 2.0, 3.0);
+        let normal = flat::Vector3::new(0.0, 0.0, 1.0);
+        let touch = flat::Touch::create(
+            &mut builder,
+            &flat::TouchArgs {
+                playerName: Some(player_name),
+                gameSeconds: 12.5,
+                location: Some(&location),
+                normal: Some(&normal),
+                team: 1,
+                playerIndex: 3,
+            },
+        );
+        builder.finish(touch, None);
+
+        let touch = flatbuffers::get_root::<flat::Touch<'_>>(builder.finished_data());
+        let Touch {
+            player_name,
+            game_seconds,
+            location,
+            normal,
+            team,
+            player_index,
+            _non_exhaustive,
+        } = Touch::try_from(touch).unwrap();
+        assert_eq!(player_name, "Chell");
+        assert_eq!(game_seconds, 12.5);
+        assert_eq!(location, Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!(normal, Vector3::new(0.0, 0.0, 1.0));
+        assert_eq!((team, player_index), (1, 3));
+    }
+}
@@ -0,0 +1,359 @@
+//! Borrowed, lazily-decoded views of game data.
+//!
+//! These read straight out of the flatbuffer, so nothing is allocated and only
+//! the fields you ask for are decoded. Fields which are plain data (like
+//! [`Physics`] or [`GameInfo`]) come back as the same owned types used by
+//! [`GameTickPacket`]; only fields that would otherwise need an allocation are
+//! wrapped in a view.
+
+#![allow(missing_docs)]
+
+use crate::{error::InvalidFlatbufferError, flat, game::*, game_deserialize, verify};
+use flatbuffers::ForwardsUOffset;
+use std::{convert::TryFrom, ops::Range};
+
+const VERIFIED: &str = "buffer should have been verified";
+
+/// A borrowed view of a [`GameTickPacket`].
+///
+/// This can be obtained from [`Packeteer::next_view`](crate::Packeteer::next_view),
+/// or from a raw buffer with
+/// [`from_flatbuffer`](GameTickPacketView::from_flatbuffer).
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let rlbot = rlbot::init()?;
+/// let mut packets = rlbot.packeteer();
+/// loop {
+///     let packet = packets.next_view()?;
+///     for player in &packet.players() {
+///         println!("{} is at {}", player.name(), player.physics().location.x);
+///     }
+/// }
+/// # }
+/// ```
+pub struct GameTickPacketView<'buf> {
+    packet: flat::GameTickPacket<'buf>,
+}
+
+impl<'buf> GameTickPacketView<'buf> {
+    /// Verifies a raw [`flat::GameTickPacket`] buffer, and returns a view of
+    /// it.
+    pub fn from_flatbuffer(buf: &'buf [u8]) -> Result<Self, InvalidFlatbufferError> {
+        verify::game_tick_packet(buf)?;
+        Ok(Self::from_verified(buf))
+    }
+
+    /// Returns a view of a buffer which has already been passed through
+    /// [`verify::game_tick_packet`].
+    pub(crate) fn from_verified(buf: &'buf [u8]) -> Self {
+        Self {
+            packet: flatbuffers::get_root::<flat::GameTickPacket<'_>>(buf),
+        }
+    }
+
+    pub fn players(&self) -> ListView<'buf, PlayerInfoView<'buf>> {
+        self.list(flat::GameTickPacket::VT_PLAYERS).expect(VERIFIED)
+    }
+
+    pub fn boost_pad_states(&self) -> ListView<'buf, BoostPadState> {
+        self.list(flat::GameTickPacket::VT_BOOSTPADSTATES)
+            .expect(VERIFIED)
+    }
+
+    /// Info about the ball. This is usually present, but can be `None` at these
+    /// times:
+    ///
+    /// - Briefly at the start of a goal replay
+    /// - During PodiumSpotlight when the winning team is (ideally) celebrating.
+    /// - Possibly at other times not during normal gameplay.
+    pub fn ball(&self) -> Option<BallInfoView<'buf>> {
+        self.packet.ball().map(|info| BallInfoView { info })
+    }
+
+    pub fn game_info(&self) -> GameInfo {
+        self.packet.gameInfo().expect(VERIFIED).into()
+    }
+
+    /// The state of each dropshot tile. This will be `None` if the current game
+    /// is not a Dropshot game.
+    pub fn tile_information(&self) -> Option<ListView<'buf, DropshotTile>> {
+        self.list(flat::GameTickPacket::VT_TILEINFORMATION)
+    }
+
+    pub fn teams(&self) -> ListView<'buf, TeamInfo> {
+        self.list(flat::GameTickPacket::VT_TEAMS).expect(VERIFIED)
+    }
+
+    /// Decodes the entire packet into an owned [`GameTickPacket`].
+    pub fn to_packet(&self) -> GameTickPacket {
+        GameTickPacket::try_from(self.packet).expect(VERIFIED)
+    }
+
+    // The generated accessors tie vectors to the lifetime of the table
+    // reference rather than the buffer, so read them from the table directly.
+    fn list<T: FromFlat<'buf>>(&self, field: flatbuffers::VOffsetT) -> Option<ListView<'buf, T>> {
+        self.packet
+            ._tab
+            .get::<ForwardsUOffset<flatbuffers::Vector<'buf, ForwardsUOffset<T::Flat>>>>(
+                field, None,
+            )
+            .map(ListView::new)
+    }
+}
+
+/// A borrowed view of a [`PlayerInfo`].
+pub struct PlayerInfoView<'buf> {
+    info: flat::PlayerInfo<'buf>,
+}
+
+impl<'buf> PlayerInfoView<'buf> {
+    pub fn physics(&self) -> Physics {
+        Physics::try_from(self.info.physics().expect(VERIFIED)).expect(VERIFIED)
+    }
+
+    pub fn score_info(&self) -> ScoreInfo {
+        self.info.scoreInfo().expect(VERIFIED).into()
+    }
+
+    pub fn is_demolished(&self) -> bool {
+        self.info.isDemolished()
+    }
+
+    /// True if your wheels are on the ground, the wall, or the ceiling. False
+    /// if you're midair or turtling.
+    pub fn has_wheel_contact(&self) -> bool {
+        self.info.hasWheelContact()
+    }
+
+    pub fn is_supersonic(&self) -> bool {
+        self.info.isSupersonic()
+    }
+
+    pub fn is_bot(&self) -> bool {
+        self.info.isBot()
+    }
+
+    /// True if the player has jumped. Falling off the ceiling / driving off the
+    /// goal post does not count.
+    pub fn jumped(&self) -> bool {
+        self.info.jumped()
+    }
+
+    /// True if player has double jumped. False does not mean you have a jump
+    /// remaining, because the aerial timer can run out, and that doesn't
+    /// affect this flag.
+    pub fn double_jumped(&self) -> bool {
+        self.info.doubleJumped()
+    }
+
+    pub fn name(&self) -> &'buf str {
+        self.info.name().expect(VERIFIED)
+    }
+
+    pub fn team(&self) -> i32 {
+        self.info.team()
+    }
+
+    pub fn boost(&self) -> i32 {
+        self.info.boost()
+    }
+
+    /// The dimensions of the car's hitbox. This may be `None` with older
+    /// versions of RLBot.
+    pub fn hitbox(&self) -> Option<BoxShape> {
+        self.info.hitbox().map(BoxShape::from)
+    }
+}
+
+/// A borrowed view of a [`BallInfo`].
+pub struct BallInfoView<'buf> {
+    info: flat::BallInfo<'buf>,
+}
+
+impl<'buf> BallInfoView<'buf> {
+    pub fn physics(&self) -> Physics {
+        Physics::try_from(self.info.physics().expect(VERIFIED)).expect(VERIFIED)
+    }
+
+    pub fn latest_touch(&self) -> Option<TouchView<'buf>> {
+        self.info.latestTouch().map(|touch| TouchView { touch })
+    }
+
+    pub fn dropshot_info(&self) -> Option<DropshotBallInfo> {
+        self.info.dropShotInfo().map(DropshotBallInfo::from)
+    }
+
+    /// The shape of the ball, which changes with the
+    /// [`BallTypeOption`](crate::BallTypeOption) mutator. This may be `None`
+    /// with older versions of RLBot.
+    pub fn shape(&self) -> Option<CollisionShape> {
+        game_deserialize::collision_shape(self.info.shape_type(), self.info.shape())
+    }
+}
+
+/// A borrowed view of a [`Touch`].
+pub struct TouchView<'buf> {
+    touch: flat::Touch<'buf>,
+}
+
+impl<'buf> TouchView<'buf> {
+    /// The name of the player involved with the touch.
+    pub fn player_name(&self) -> &'buf str {
+        self.touch.playerName().expect(VERIFIED)
+    }
+
+    /// Seconds that had elapsed in the game when the touch occurred.
+    pub fn game_seconds(&self) -> f32 {
+        self.touch.gameSeconds()
+    }
+
+    /// The point of contact for the touch.
+    pub fn location(&self) -> Vector3 {
+        self.touch.location().expect(VERIFIED).into()
+    }
+
+    /// The direction of the touch.
+    pub fn normal(&self) -> Vector3 {
+        self.touch.normal().expect(VERIFIED).into()
+    }
+
+    /// The Team which the touch belongs to, 0 for blue 1 for orange.
+    pub fn team(&self) -> i32 {
+        self.touch.team()
+    }
+
+    /// The index of the player involved with the touch.
+    pub fn player_index(&self) -> i32 {
+        self.touch.playerIndex()
+    }
+}
+
+/// A borrowed list of items, which are decoded one at a time as they are
+/// accessed.
+pub struct ListView<'buf, T: FromFlat<'buf>> {
+    vector: flatbuffers::Vector<'buf, ForwardsUOffset<T::Flat>>,
+}
+
+impl<'buf, T: FromFlat<'buf>> ListView<'buf, T> {
+    fn new(vector: flatbuffers::Vector<'buf, ForwardsUOffset<T::Flat>>) -> Self {
+        Self { vector }
+    }
+
+    /// Returns the number of items in the list.
+    pub fn len(&self) -> usize {
+        self.vector.len()
+    }
+
+    /// Returns true if the list contains no items.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the item at `index`, or `None` if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<T> {
+        if index < self.len() {
+            Some(T::from_flat(self.vector.get(index)))
+        } else {
+            None
+        }
+    }
+
+    /// Iterates over the items in the list.
+    pub fn iter(&self) -> ListIter<'_, 'buf, T> {
+        ListIter {
+            list: self,
+            range: 0..self.len(),
+        }
+    }
+}
+
+impl<'a, 'buf, T: FromFlat<'buf>> IntoIterator for &'a ListView<'buf, T> {
+    type Item = T;
+    type IntoIter = ListIter<'a, 'buf, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// An iterator over the items in a [`ListView`].
+pub struct ListIter<'a, 'buf, T: FromFlat<'buf>> {
+    list: &'a ListView<'buf, T>,
+    range: Range<usize>,
+}
+
+impl<'a, 'buf, T: FromFlat<'buf>> Iterator for ListIter<'a, 'buf, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.range
+            .next()
+            .map(|i| T::from_flat(self.list.vector.get(i)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a, 'buf, T: FromFlat<'buf>> ExactSizeIterator for ListIter<'a, 'buf, T> {}
+
+pub use self::sealed::FromFlat;
+
+mod sealed {
+    use crate::{flat, game::*, game_view::*};
+
+    /// A type which can be read out of a flatbuffer table by a [`ListView`].
+    ///
+    /// This trait is sealed and cannot be implemented outside this crate.
+    pub trait FromFlat<'buf>: Sealed {
+        #[doc(hidden)]
+        type Flat: flatbuffers::Follow<'buf, Inner = Self::Flat> + 'buf;
+
+        #[doc(hidden)]
+        fn from_flat(flat: Self::Flat) -> Self;
+    }
+
+    /// Not exported, so nothing outside this crate can implement [`FromFlat`].
+    pub trait Sealed {}
+
+    impl<'buf> Sealed for PlayerInfoView<'buf> {}
+    impl Sealed for BoostPadState {}
+    impl Sealed for DropshotTile {}
+    impl Sealed for TeamInfo {}
+
+    impl<'buf> FromFlat<'buf> for PlayerInfoView<'buf> {
+        type Flat = flat::PlayerInfo<'buf>;
+
+        fn from_flat(info: Self::Flat) -> Self {
+            Self { info }
+        }
+    }
+
+    impl<'buf> FromFlat<'buf> for BoostPadState {
+        type Flat = flat::BoostPadState<'buf>;
+
+        fn from_flat(state: Self::Flat) -> Self {
+            state.into()
+        }
+    }
+
+    impl<'buf> FromFlat<'buf> for DropshotTile {
+        type Flat = flat::DropshotTile<'buf>;
+
+        fn from_flat(tile: Self::Flat) -> Self {
+            tile.into()
+        }
+    }
+
+    impl<'buf> FromFlat<'buf> for TeamInfo {
+        type Flat = flat::TeamInfo<'buf>;
+
+        fn from_flat(info: Self::Flat) -> Self {
+            info.into()
+        }
+    }
+}
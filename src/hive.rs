@@ -1,9 +1,15 @@
 use crate::{
-    framework::parse_version_and_directory,
+    debug_draw::DebugDrawFlusher,
+    error::{Error, FrameworkArgsError, TimingViolation},
+    framework::{
+        expect_argument, expect_value, parse_value, parse_version_and_directory, RunOptions,
+    },
     game::{ControllerState, GameTickPacket},
-    init_with_options, InitOptions,
+    init_with_options,
+    rlbot::RLBot,
+    InitOptions,
 };
-use std::{env, error::Error, path::PathBuf};
+use std::{env, path::PathBuf};
 
 // Most of this is basically a copy of what is in framework.rs,
 // just adapted for hivemind.
@@ -25,6 +31,16 @@ pub trait Hivemind {
     /// The usize is the bot index you want to send the inputs to, and the
     /// ControllerState is what you want that bot to do.
     fn tick(&mut self, packet: &GameTickPacket) -> Vec<(usize, ControllerState)>;
+
+    /// This is called in lockstep mode, after the inputs for a packet were
+    /// sent later than the [deadline](RunOptions::tick_deadline).
+    ///
+    /// By default, this prints the violation and carries on. To stop the game
+    /// loop instead, return [`Error::TimingViolation`].
+    fn timing_violation(&mut self, violation: TimingViolation) -> Result<(), Error> {
+        println!("{}", violation);
+        Ok(())
+    }
 }
 
 /// Runs your hivemind bot. Pass in your struct which implements the Hivemind
@@ -34,31 +50,62 @@ pub trait Hivemind {
 /// The usize is the bot index you want to send the inputs to. Order does not
 /// matter. You will get warnings if you try sending the wrong number of inputs
 /// or give indices that are not in your drone_indices.
-pub fn run_hive<H: Hivemind>(mut hive: H) -> Result<(), Box<dyn Error>> {
-    let args = parse_hive_framework_args()
-        .map_err(|_| Box::<dyn Error>::from("could not parse framework arguments"))?
-        .ok_or_else(|| Box::<dyn Error>::from("not launched by framework"))?;
+pub fn run_hive<H: Hivemind>(hive: H) -> Result<(), Error> {
+    run_hive_with(hive, RunOptions::default())
+}
+
+/// Like [`run_hive`], but runs the game loop according to the given
+/// [`RunOptions`].
+pub fn run_hive_with<H: Hivemind>(mut hive: H, options: RunOptions) -> Result<(), Error> {
+    let args = parse_hive_framework_args()?.ok_or(Error::NotLaunchedByFramework)?;
 
     let rlbot = init_with_options(From::from(&args))?;
 
+    run_hive_loop(&rlbot, &args.drone_indices, &mut hive, &options)
+}
+
+/// Runs the game loop for a hivemind which controls the given players, using
+/// an `RLBot` which has already been initialized.
+///
+/// This is what [`run_hive_with`] does after connecting to the framework. It
+/// only returns on error.
+///
+/// In lockstep mode, the game waits for every drone, so any drone missing from
+/// the output of [`tick`](Hivemind::tick) is sent a neutral
+/// [`ControllerState`].
+pub fn run_hive_loop<H: Hivemind>(
+    rlbot: &RLBot,
+    drone_indices: &[i32],
+    hive: &mut H,
+    options: &RunOptions,
+) -> Result<(), Error> {
     // Create a Vec<usize> copy of the original Vec<i32>.
-    let drone_indices = &args
-        .drone_indices
+    let drone_indices = &drone_indices
         .iter()
         .map(|&x| x as usize)
         .collect::<Vec<usize>>();
 
     hive.set_drone_indices(drone_indices.clone());
 
-    let mut packets = rlbot.packeteer();
+    let mut packets = options.packeteer(rlbot);
+    // The hivemind draws as its first drone.
+    let mut debug_draw = DebugDrawFlusher::new(drone_indices.first().map_or(0, |&i| i as i32));
     loop {
         let packet = packets.next()?;
-        let inputs = hive.tick(&packet);
+        let started = packets.fetched();
+        let mut inputs = hive.tick(&packet);
 
         // Checks to make sure that everything is valid.
         if inputs.len() != drone_indices.len() {
             println!("number of received controller states does not match the number of drones")
         }
+        if options.lockstep {
+            for &drone in drone_indices {
+                if !inputs.iter().any(|&(index, _)| index == drone) {
+                    inputs.push((drone, ControllerState::default()));
+                }
+            }
+        }
         let valid_inputs = inputs.into_iter().filter_map(|(index, input)| {
             if drone_indices.contains(&index) && index <= (std::i32::MAX as usize) {
                 Some((index as i32, input))
@@ -72,6 +119,11 @@ pub fn run_hive<H: Hivemind>(mut hive: H) -> Result<(), Box<dyn Error>> {
 
         // Sending valid inputs to drones.
         rlbot.update_multiple_inputs(valid_inputs)?;
+
+        if let Some(violation) = options.check_deadline(&packet, started) {
+            hive.timing_violation(violation)?;
+        }
+        debug_draw.flush(rlbot);
     }
 }
 
@@ -84,14 +136,14 @@ pub fn run_hive<H: Hivemind>(mut hive: H) -> Result<(), Box<dyn Error>> {
 /// * `Ok(None)` – if the app was *not* launched by the framework.
 /// * `Err(_)` – if it appears the app was launched by the framework, but we
 ///   could not understand the arguments.
-pub fn parse_hive_framework_args() -> Result<Option<HiveFrameworkArgs>, ()> {
+pub fn parse_hive_framework_args() -> Result<Option<HiveFrameworkArgs>, FrameworkArgsError> {
     parse_framework_command_line(env::args().skip(1))
 }
 
 // Same as in framework.rs except it's for a hivemind.
 fn parse_framework_command_line(
     mut args: impl Iterator<Item = String>,
-) -> Result<Option<HiveFrameworkArgs>, ()> {
+) -> Result<Option<HiveFrameworkArgs>, FrameworkArgsError> {
     let (rlbot_version, rlbot_dll_directory) =
         if let Some(val) = parse_version_and_directory(&mut args)? {
             val
@@ -99,17 +151,12 @@ fn parse_framework_command_line(
             return Ok(None);
         };
 
-    if args.next().as_ref().map(|s| &s[..]) != Some("--drone-indices") {
-        return Err(());
-    }
+    expect_argument(&mut args, "--drone-indices")?;
     // drone indices are comma separated integers, e.g. "0,1,2,3,4,5"
-    let drone_indices = args
-        .next()
-        .ok_or(())?
+    let drone_indices = expect_value(&mut args, "--drone-indices")?
         .split(',')
-        .map(|x| x.parse::<i32>())
-        .collect::<Result<Vec<i32>, _>>()
-        .map_err(|_| ())?;
+        .map(|x| parse_value("--drone-indices", x))
+        .collect::<Result<Vec<i32>, _>>()?;
 
     Ok(Some(HiveFrameworkArgs {
         rlbot_version,
@@ -150,7 +197,7 @@ impl From<&HiveFrameworkArgs> for InitOptions {
 mod tests {
     use super::*;
 
-    fn pfcl(ss: Vec<&str>) -> Result<Option<HiveFrameworkArgs>, ()> {
+    fn pfcl(ss: Vec<&str>) -> Result<Option<HiveFrameworkArgs>, FrameworkArgsError> {
         parse_framework_command_line(ss.into_iter().map(str::to_string))
     }
 
@@ -190,5 +237,23 @@ mod tests {
 
         let args = pfcl(vec!["--rlbot-version", "1.35.5"]);
         assert!(args.is_err());
+
+        let args = pfcl(vec![
+            "--rlbot-version",
+            "1.35.5",
+            "--rlbot-dll-directory",
+            "/tmp",
+            "--drone-indices",
+            "0,,2",
+        ]);
+        match args {
+            Err(FrameworkArgsError::InvalidValue {
+                argument, value, ..
+            }) => {
+                assert_eq!(argument, "--drone-indices");
+                assert_eq!(value, "");
+            }
+            _ => panic!(),
+        }
     }
 }
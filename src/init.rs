@@ -1,11 +1,18 @@
-use crate::{dll::RLBotCoreInterface, interface::RLBotInterface, rlbot::RLBot};
-use std::{error::Error, path::PathBuf, thread::sleep, time::Duration};
+use crate::{
+    backend::CoreBackend,
+    dll::RLBotCoreInterface,
+    error::{Error, TimeoutError},
+    interface::RLBotInterface,
+    replay::RecordingBackend,
+    rlbot::RLBot,
+};
+use std::{fs::File, path::PathBuf, thread::sleep, time::Duration};
 
 /// Initializes RLBot and returns a ready-to-use [`RLBot`] object.
 ///
 /// This function works exactly as [`init_with_options`]. Take a look there for
 /// more details.
-pub fn init() -> Result<RLBot, Box<dyn Error>> {
+pub fn init() -> Result<RLBot, Error> {
     init_with_options(Default::default())
 }
 
@@ -44,30 +51,61 @@ pub fn init() -> Result<RLBot, Box<dyn Error>> {
 ///
 /// [`examples/simple`]: https://github.com/whatisaphone/rlbot-rust/blob/master/examples/simple.rs
 #[allow(clippy::needless_pass_by_value)]
-pub fn init_with_options(options: InitOptions) -> Result<RLBot, Box<dyn Error>> {
+pub fn init_with_options(options: InitOptions) -> Result<RLBot, Error> {
     let rlbot_dll_directory = options.rlbot_dll_directory.as_deref();
 
     let dll = RLBotCoreInterface::load(rlbot_dll_directory)?;
-    wait_for_initialized(&dll)?;
+    match &options.record_to {
+        Some(path) => init_with_backend(RecordingBackend::new(dll, File::create(path)?)?),
+        None => init_with_backend(dll),
+    }
+}
+
+/// Initializes RLBot on top of a custom [`CoreBackend`] instead of the RLBot
+/// DLL.
+///
+/// Unlike [`init_with_options`], this can be called any number of times, since
+/// nothing is injected into the game.
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let backend = rlbot::MockBackend::new();
+/// let rlbot = rlbot::init_with_backend(backend.clone())?;
+///
+/// rlbot.start_match(&rlbot::MatchSettings::rlbot_vs_allstar("Hero", "Villain"))?;
+/// assert_eq!(backend.match_settings().len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn init_with_backend(backend: impl CoreBackend + 'static) -> Result<RLBot, Error> {
+    wait_for_initialized(&backend)?;
 
-    Ok(RLBot::new(RLBotInterface::new(dll)))
+    Ok(RLBot::new(RLBotInterface::new(Box::new(backend))))
 }
 
-fn wait_for_initialized(dll: &RLBotCoreInterface) -> Result<(), Box<dyn Error>> {
-    for _ in 0..100 {
-        if (dll.is_initialized)() {
+fn wait_for_initialized(backend: &dyn CoreBackend) -> Result<(), Error> {
+    const INTERVAL: Duration = Duration::from_millis(10);
+    const ATTEMPTS: u32 = 100;
+
+    for _ in 0..ATTEMPTS {
+        if backend.is_initialized() {
             return Ok(());
         }
-        sleep(Duration::from_millis(10));
+        sleep(INTERVAL);
     }
 
-    Err(From::from("RLBot did not become initialized"))
+    Err(Error::InitializationTimeout(TimeoutError {
+        timeout: INTERVAL * ATTEMPTS,
+    }))
 }
 
 /// Options for customizing the way the framework is initialized.
 #[derive(Default)]
 pub struct InitOptions {
     rlbot_dll_directory: Option<PathBuf>,
+    record_to: Option<PathBuf>,
 }
 
 impl InitOptions {
@@ -87,4 +125,15 @@ impl InitOptions {
         self.rlbot_dll_directory = Some(rlbot_dll_directory.into());
         self
     }
+
+    /// Records every packet, physics tick, field info and ball prediction
+    /// received from RLBot to the given file.
+    ///
+    /// The recording can be played back later with
+    /// [`ReplayBackend`](crate::ReplayBackend). See
+    /// [`RecordingBackend`](crate::RecordingBackend) for details.
+    pub fn record_to(mut self, path: impl Into<PathBuf>) -> Self {
+        self.record_to = Some(path.into());
+        self
+    }
 }
@@ -1,23 +1,30 @@
-use crate::{dll::RLBotCoreInterface, error::RLBotError, ffi, flat, game::*};
-use std::{
-    os::raw::{c_int, c_void},
-    ptr::null_mut,
+use crate::{
+    backend::CoreBackend,
+    error::{InvalidFlatbufferError, RLBotError},
+    ffi, flat,
+    game::*,
+    game_deserialize, verify,
 };
+use std::{convert::TryInto, os::raw::c_int};
 
 pub struct RLBotInterface {
-    dll: RLBotCoreInterface,
+    backend: Box<dyn CoreBackend>,
 }
 
 impl RLBotInterface {
-    pub fn new(dll: RLBotCoreInterface) -> Self {
-        Self { dll }
+    pub(crate) fn new(backend: Box<dyn CoreBackend>) -> Self {
+        Self { backend }
     }
 
     /// Grabs the current [`flat::FieldInfo`] from RLBot, if any
-    pub fn update_field_info_flatbuffer(&self) -> Option<FieldInfo> {
-        self.dll
-            .update_field_info_flatbuffer()
-            .map(|buf| flatbuffers::get_root::<flat::FieldInfo<'_>>(&buf).into())
+    pub fn update_field_info_flatbuffer(
+        &self,
+    ) -> Result<Option<FieldInfo>, InvalidFlatbufferError> {
+        decode(
+            self.backend.update_field_info_flatbuffer(),
+            verify::field_info,
+            |buf| flatbuffers::get_root::<flat::FieldInfo<'_>>(buf).try_into(),
+        )
     }
 
     /// Grabs the current [`ffi::FieldInfo`] from RLBot
@@ -25,17 +32,27 @@ impl RLBotInterface {
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     pub fn update_field_info(&self, field_info: &mut ffi::FieldInfo) -> Result<(), RLBotError> {
-        let status = (self.dll.update_field_info)(field_info);
+        let status = self.backend.update_field_info(field_info);
         core_result(status)
     }
 
     /// Grabs the current [`flat::GameTickPacket`] from RLBot,
     /// if any. Consider using [`packeteer`](RLBot::packeteer) instead for
     /// a more convenient interface.
-    pub fn update_live_data_packet_flatbuffer(&self) -> Option<GameTickPacket> {
-        self.dll
-            .update_live_data_packet_flatbuffer()
-            .map(|buf| flatbuffers::get_root::<flat::GameTickPacket<'_>>(&buf).into())
+    pub fn update_live_data_packet_flatbuffer(
+        &self,
+    ) -> Result<Option<GameTickPacket>, InvalidFlatbufferError> {
+        decode(
+            self.backend.update_live_data_packet_flatbuffer(),
+            verify::game_tick_packet,
+            |buf| flatbuffers::get_root::<flat::GameTickPacket<'_>>(buf).try_into(),
+        )
+    }
+
+    /// Copies the current raw [`flat::GameTickPacket`] into `buf`. Returns
+    /// false if there is no packet.
+    pub(crate) fn update_live_data_packet_flatbuffer_into(&self, buf: &mut Vec<u8>) -> bool {
+        self.backend.update_live_data_packet_flatbuffer_into(buf)
     }
 
     /// Grabs the current [`LiveDataPacket`](ffi::LiveDataPacket) from RLBot.
@@ -48,15 +65,19 @@ impl RLBotInterface {
         &self,
         packet: &mut ffi::LiveDataPacket,
     ) -> Result<(), RLBotError> {
-        let status = (self.dll.update_live_data_packet)(packet);
+        let status = self.backend.update_live_data_packet(packet);
         core_result(status)
     }
 
     /// Grabs the current physics tick as a FlatBuffer table.
-    pub fn update_rigid_body_tick_flatbuffer(&self) -> Option<RigidBodyTick> {
-        self.dll
-            .update_rigid_body_tick_flatbuffer()
-            .map(|buf| flatbuffers::get_root::<flat::RigidBodyTick<'_>>(&buf).into())
+    pub fn update_rigid_body_tick_flatbuffer(
+        &self,
+    ) -> Result<Option<RigidBodyTick>, InvalidFlatbufferError> {
+        decode(
+            self.backend.update_rigid_body_tick_flatbuffer(),
+            verify::rigid_body_tick,
+            |buf| flatbuffers::get_root::<flat::RigidBodyTick<'_>>(buf).try_into(),
+        )
     }
 
     /// Grabs the current physics tick as a struct.
@@ -64,17 +85,14 @@ impl RLBotInterface {
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     pub fn update_rigid_body_tick(&self, tick: &mut ffi::RigidBodyTick) -> Result<(), RLBotError> {
-        let status = (self.dll.update_rigid_body_tick)(tick);
+        let status = self.backend.update_rigid_body_tick(tick);
         core_result(status)
     }
 
     /// Sets the desired game state. The buffer must be built from a
     /// [`flat::DesiredGameState`]
     pub fn set_game_state(&self, desired_game_state_buffer: &[u8]) -> Result<(), RLBotError> {
-        let status = (self.dll.set_game_state)(
-            desired_game_state_buffer.as_ptr() as *mut c_void,
-            desired_game_state_buffer.len() as c_int,
-        );
+        let status = self.backend.set_game_state(desired_game_state_buffer);
         core_result(status)
     }
 
@@ -83,29 +101,44 @@ impl RLBotInterface {
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     pub fn start_match(&self, match_settings: ffi::MatchSettings) -> Result<(), RLBotError> {
-        let status = (self.dll.start_match)(match_settings, None, null_mut());
+        let status = self.backend.start_match(match_settings);
         core_result(status)
     }
 
     /// Tell RLBot to start a match. The buffer must be built from a
     /// [`flat::QuickChat`].
     pub fn start_match_flatbuffer(&self, match_settings_buffer: &[u8]) -> Result<(), RLBotError> {
-        let status = (self.dll.start_match_flatbuffer)(
-            match_settings_buffer.as_ptr() as *mut c_void,
-            match_settings_buffer.len() as c_int,
-        );
+        let status = self.backend.start_match_flatbuffer(match_settings_buffer);
         core_result(status)
     }
 
     /// Send a quickchat. The buffer must be built from a [`flat::QuickChat`]
     pub fn send_quick_chat(&self, quick_chat_buffer: &[u8]) -> Result<(), RLBotError> {
-        let status = (self.dll.send_quick_chat)(
-            quick_chat_buffer.as_ptr() as *mut c_void,
-            quick_chat_buffer.len() as c_int,
-        );
+        let status = self.backend.send_quick_chat(quick_chat_buffer);
         core_result(status)
     }
 
+    /// Grabs the quick chats that the given player can see, whose message
+    /// index is greater than `last_message_index`. Consider using
+    /// [`quick_chat_inbox`](RLBot::quick_chat_inbox) instead for a more
+    /// convenient interface.
+    pub fn receive_chat(
+        &self,
+        player_index: i32,
+        team: i32,
+        last_message_index: i32,
+    ) -> Result<Option<Vec<QuickChat>>, InvalidFlatbufferError> {
+        decode(
+            self.backend
+                .receive_chat(player_index, team, last_message_index),
+            verify::quick_chat_messages,
+            |buf| {
+                let messages = flatbuffers::get_root::<flat::QuickChatMessages<'_>>(buf);
+                Ok(game_deserialize::quick_chat_messages(messages))
+            },
+        )
+    }
+
     /// Sends player input to RLBot.
     #[deprecated(
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
@@ -115,7 +148,7 @@ impl RLBotInterface {
         player_input: ffi::PlayerInput,
         player_index: c_int,
     ) -> Result<(), RLBotError> {
-        let status = (self.dll.update_player_input)(player_input, player_index);
+        let status = self.backend.update_player_input(player_input, player_index);
         core_result(status)
     }
 
@@ -125,20 +158,16 @@ impl RLBotInterface {
         &self,
         player_input_buffer: &[u8],
     ) -> Result<(), RLBotError> {
-        let status = (self.dll.update_player_input_flatbuffer)(
-            player_input_buffer.as_ptr() as *mut c_void,
-            player_input_buffer.len() as c_int,
-        );
+        let status = self
+            .backend
+            .update_player_input_flatbuffer(player_input_buffer);
         core_result(status)
     }
 
     /// Render a group of lines/text. The buffer must be built from a
     /// [`flat::RenderGroup`]
     pub fn render_group(&self, render_group_buffer: &[u8]) -> Result<(), RLBotError> {
-        let status = (self.dll.render_group)(
-            render_group_buffer.as_ptr() as *mut c_void,
-            render_group_buffer.len() as c_int,
-        );
+        let status = self.backend.render_group(render_group_buffer);
         core_result(status)
     }
 
@@ -147,10 +176,12 @@ impl RLBotInterface {
     ///
     /// Note that this method requires the framework's `BallPrediction.exe` to
     /// be running in the background.
-    pub fn get_ball_prediction(&self) -> Option<BallPrediction> {
-        self.dll
-            .get_ball_prediction()
-            .map(|buf| flatbuffers::get_root::<flat::BallPrediction<'_>>(&buf).into())
+    pub fn get_ball_prediction(&self) -> Result<Option<BallPrediction>, InvalidFlatbufferError> {
+        decode(
+            self.backend.get_ball_prediction(),
+            verify::ball_prediction,
+            |buf| flatbuffers::get_root::<flat::BallPrediction<'_>>(buf).try_into(),
+        )
     }
 
     /// Gets the framework's current prediction of ball motion as a struct.
@@ -164,11 +195,27 @@ impl RLBotInterface {
         &self,
         result: &mut ffi::BallPredictionPacket,
     ) -> Result<(), RLBotError> {
-        let status = (self.dll.get_ball_prediction_struct)(result);
+        let status = self.backend.get_ball_prediction_struct(result);
         core_result(status)
     }
 }
 
+/// Verifies a buffer from the backend, and then converts it into one of our
+/// types.
+fn decode<T>(
+    buf: Option<Vec<u8>>,
+    verify: fn(&[u8]) -> Result<(), InvalidFlatbufferError>,
+    convert: fn(&[u8]) -> Result<T, InvalidFlatbufferError>,
+) -> Result<Option<T>, InvalidFlatbufferError> {
+    match buf {
+        Some(buf) => {
+            verify(&buf)?;
+            convert(&buf).map(Some)
+        }
+        None => Ok(None),
+    }
+}
+
 fn core_result(status: ffi::RLBotCoreStatus) -> Result<(), RLBotError> {
     match status {
         ffi::RLBotCoreStatus::Success => Ok(()),
@@ -127,20 +127,46 @@
 #![warn(missing_docs, clippy::all)]
 #![allow(intra_doc_link_resolution_failure)]
 
+#[cfg(feature = "async")]
+pub use crate::stream::{PacketStream, PhysicsStream};
 pub use crate::{
-    framework::{parse_framework_args, run_bot, Bot, FrameworkArgs},
+    backend::CoreBackend,
+    ball_sim::BallPredictor,
+    debug_draw::DebugDraw,
+    error::{
+        ConfigError, Error, FrameworkArgsError, InvalidFlatbufferError, ParseColorError,
+        RLBotError, RateLimitError, TimeoutError, TimingViolation,
+    },
+    framework::{
+        parse_framework_args, run_bot, run_bot_loop, run_bot_with, Bot, FrameworkArgs, RunOptions,
+    },
     game::*,
-    hive::{parse_hive_framework_args, run_hive, Hivemind, HiveFrameworkArgs},
-    init::{init, init_with_options, InitOptions},
+    game_view::{
+        BallInfoView, FromFlat, GameTickPacketView, ListIter, ListView, PlayerInfoView, TouchView,
+    },
+    hive::{
+        parse_hive_framework_args, run_hive, run_hive_loop, run_hive_with, HiveFrameworkArgs,
+        Hivemind,
+    },
+    init::{init, init_with_backend, init_with_options, InitOptions},
     match_settings::*,
-    packeteer::Packeteer,
+    math::RotationMatrix,
+    mock::MockBackend,
+    packeteer::{Packeteer, PacketeerOptions},
     physicist::Physicist,
+    quick_chat::{QuickChatInbox, QuickChatSelection},
     render::{Color, RenderGroup},
+    render_manager::RenderManager,
+    replay::{RecordingBackend, ReplayBackend, ReplayMode},
     rlbot::RLBot,
     rlbot_generated::rlbot::flat,
     state::*,
 };
 
+mod backend;
+mod ball_sim;
+mod collision;
+mod debug_draw;
 mod dll;
 mod error;
 pub mod ffi;
@@ -148,18 +174,36 @@ mod ffi_impls;
 mod framework;
 mod game;
 mod game_deserialize;
+mod game_view;
 mod hive;
 mod init;
 mod interface;
+mod match_config;
 mod match_settings;
+mod math;
+#[cfg(feature = "glam")]
+mod math_glam;
+#[cfg(feature = "nalgebra")]
+mod math_nalgebra;
+mod mock;
+mod orientation;
 mod packeteer;
 mod physicist;
+mod quick_chat;
 mod render;
+mod render_manager;
+mod render_shapes;
+mod replay;
 mod rlbot;
 #[allow(non_camel_case_types, non_snake_case, missing_docs, clippy::all)]
 mod rlbot_generated;
+#[cfg(feature = "serde")]
+mod serde_impls;
 mod state;
 mod state_convert;
 #[cfg(feature = "nalgebra")]
 mod state_nalgebra;
+#[cfg(feature = "async")]
+mod stream;
 mod utils;
+mod verify;
@@ -0,0 +1,659 @@
+//! Loading [`MatchSettings`] from the framework's `rlbot.cfg` format.
+
+use crate::{
+    error::ConfigError,
+    flat::BoostStrengthOption,
+    match_settings::{
+        BallBouncinessOption, BallMaxSpeedOption, BallSizeOption, BallTypeOption, BallWeightOption,
+        BoostOption, DemolishOption, ExistingMatchBehavior, GameMap, GameMode, GameSpeedOption,
+        GravityOption, LoadoutPaint, MatchLength, MatchSettings, MaxScore, MutatorSettings,
+        OvertimeOption, PlayerClass, PlayerConfiguration, PlayerLoadout, RespawnTimeOption,
+        RumbleOption, SeriesLengthOption,
+    },
+};
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+const MATCH_SECTION: &str = "Match Configuration";
+const MUTATOR_SECTION: &str = "Mutator Configuration";
+const PARTICIPANT_SECTION: &str = "Participant Configuration";
+const LOCATIONS_SECTION: &str = "Locations";
+
+/// Loads an `rlbot.cfg`, along with the bot and loadout configs it refers to.
+pub(crate) fn load(path: &Path) -> Result<MatchSettings, ConfigError> {
+    let ini = Ini::load(path)?;
+    let base = path.parent().unwrap_or_else(|| Path::new(""));
+
+    let mut settings = MatchSettings::new();
+    let mut num_participants = None;
+    if let Some(section) = ini.section(MATCH_SECTION) {
+        for entry in &section.entries {
+            match entry.key.as_str() {
+                "num_participants" => num_participants = Some(ini.value::<usize>(entry)?),
+                "game_mode" => settings.game_mode = ini.lookup(entry, GAME_MODES)?,
+                "game_map" => settings.game_map = ini.lookup(entry, GAME_MAPS)?,
+                "skip_replays" => settings.skip_replays = ini.bool(entry)?,
+                "start_without_countdown" => settings.instant_start = ini.bool(entry)?,
+                "existing_match_behavior" => {
+                    settings.existing_match_behavior =
+                        ini.lookup(entry, EXISTING_MATCH_BEHAVIORS)?
+                }
+                "enable_lockstep" => settings.enable_lockstep = ini.bool(entry)?,
+                // The framework handles these itself, and `MatchSettings` has
+                // nothing to put them in, so they are only checked.
+                "enable_rendering" | "enable_state_setting" | "auto_save_replay" => {
+                    ini.bool(entry)?;
+                }
+                _ => return Err(ini.unknown(entry)),
+            }
+        }
+    }
+    let num_participants =
+        num_participants.ok_or_else(|| ini.missing(MATCH_SECTION, "num_participants"))?;
+
+    if let Some(section) = ini.section(MUTATOR_SECTION) {
+        settings.mutator_settings = mutator_settings(&ini, section)?;
+    }
+
+    let participants = participants(&ini)?;
+    let mut player_configurations = Vec::with_capacity(num_participants);
+    for index in 0..num_participants {
+        let participant = participants.get(&index).cloned().unwrap_or_default();
+        let config = participant.config.ok_or_else(|| {
+            ini.missing(
+                PARTICIPANT_SECTION,
+                &format!("participant_config_{}", index),
+            )
+        })?;
+        let team = participant.team.unwrap_or(0);
+        let skill = participant.bot_skill.unwrap_or(1.0);
+        let variety = match participant.kind.unwrap_or(ParticipantKind::RLBot) {
+            ParticipantKind::Human => PlayerClass::HumanPlayer,
+            ParticipantKind::RLBot => PlayerClass::RLBotPlayer,
+            ParticipantKind::Psyonix => PlayerClass::psyonix_bot(skill),
+            ParticipantKind::PartyMember => PlayerClass::PartyMemberBotPlayer,
+        };
+
+        let bot_path = base.join(config);
+        let bot = Ini::load(&bot_path)?;
+        let locations = bot.section(LOCATIONS_SECTION);
+        let name = locations
+            .and_then(|s| s.get("name"))
+            .ok_or_else(|| bot.missing(LOCATIONS_SECTION, "name"))?;
+        let looks = match participant.loadout_config {
+            Some(looks) => Some(base.join(looks)),
+            None => locations.and_then(|s| s.get("looks_config")).map(|looks| {
+                bot_path
+                    .parent()
+                    .unwrap_or_else(|| Path::new(""))
+                    .join(looks)
+            }),
+        };
+        let loadout = match looks {
+            Some(looks) => loadout(&Ini::load(&looks)?, team)?,
+            None => PlayerLoadout::new(),
+        };
+
+        player_configurations
+            .push(PlayerConfiguration::new(variety, name.to_string(), team).loadout(loadout));
+    }
+
+    Ok(settings.player_configurations(player_configurations))
+}
+
+fn mutator_settings(ini: &Ini, section: &Section) -> Result<MutatorSettings, ConfigError> {
+    let mut mutators = MutatorSettings::new();
+    for entry in &section.entries {
+        match entry.key.as_str() {
+            "match length" => mutators.match_length = ini.lookup(entry, MATCH_LENGTHS)?,
+            "max score" => mutators.max_score = ini.lookup(entry, MAX_SCORES)?,
+            "overtime" => mutators.overtime_option = ini.lookup(entry, OVERTIMES)?,
+            "series length" => mutators.series_length_option = ini.lookup(entry, SERIES_LENGTHS)?,
+            "game speed" => mutators.game_speed_option = ini.lookup(entry, GAME_SPEEDS)?,
+            "ball max speed" => {
+                mutators.ball_max_speed_option = ini.lookup(entry, BALL_MAX_SPEEDS)?
+            }
+            "ball type" => mutators.ball_type_option = ini.lookup(entry, BALL_TYPES)?,
+            "ball weight" => mutators.ball_weight_option = ini.lookup(entry, BALL_WEIGHTS)?,
+            "ball size" => mutators.ball_size_option = ini.lookup(entry, BALL_SIZES)?,
+            "ball bounciness" => {
+                mutators.ball_bounciness_option = ini.lookup(entry, BALL_BOUNCINESSES)?
+            }
+            "boost amount" => mutators.boost_option = ini.lookup(entry, BOOST_AMOUNTS)?,
+            "rumble" => mutators.rumble_option = ini.lookup(entry, RUMBLES)?,
+            "boost strength" => {
+                mutators.boost_strength_option = ini.lookup(entry, BOOST_STRENGTHS)?
+            }
+            "gravity" => mutators.gravity_option = ini.lookup(entry, GRAVITIES)?,
+            "demolish" => mutators.demolish_option = ini.lookup(entry, DEMOLISHES)?,
+            "respawn time" => mutators.respawn_time_option = ini.lookup(entry, RESPAWN_TIMES)?,
+            _ => return Err(ini.unknown(entry)),
+        }
+    }
+    Ok(mutators)
+}
+
+#[derive(Copy, Clone)]
+enum ParticipantKind {
+    Human,
+    RLBot,
+    Psyonix,
+    PartyMember,
+}
+
+/// Everything `rlbot.cfg` says about one participant.
+#[derive(Clone, Default)]
+struct Participant {
+    config: Option<String>,
+    team: Option<i32>,
+    kind: Option<ParticipantKind>,
+    bot_skill: Option<f32>,
+    loadout_config: Option<String>,
+}
+
+/// Collects the `participant_*_N` keys, by `N`.
+fn participants(ini: &Ini) -> Result<HashMap<usize, Participant>, ConfigError> {
+    let mut participants = HashMap::<usize, Participant>::new();
+    let section = match ini.section(PARTICIPANT_SECTION) {
+        Some(section) => section,
+        None => return Ok(participants),
+    };
+    for entry in &section.entries {
+        let key = entry.key.as_str();
+        // Check the longer prefixes first, since they contain the shorter ones.
+        if let Some(index) = indexed(key, "participant_loadout_config_") {
+            participants.entry(index).or_default().loadout_config = Some(entry.value.clone());
+        } else if let Some(index) = indexed(key, "participant_config_") {
+            participants.entry(index).or_default().config = Some(entry.value.clone());
+        } else if let Some(index) = indexed(key, "participant_team_") {
+            participants.entry(index).or_default().team = Some(ini.value(entry)?);
+        } else if let Some(index) = indexed(key, "participant_type_") {
+            participants.entry(index).or_default().kind =
+                Some(ini.lookup(entry, PARTICIPANT_KINDS)?);
+        } else if let Some(index) = indexed(key, "participant_bot_skill_") {
+            participants.entry(index).or_default().bot_skill = Some(ini.value(entry)?);
+        } else {
+            return Err(ini.unknown(entry));
+        }
+    }
+    Ok(participants)
+}
+
+/// Parses the `N` out of a key like `prefix_N`.
+fn indexed(key: &str, prefix: &str) -> Option<usize> {
+    key.strip_prefix(prefix)?.parse().ok()
+}
+
+/// Reads the loadout for the given team from a `looks.cfg`.
+fn loadout(ini: &Ini, team: i32) -> Result<PlayerLoadout, ConfigError> {
+    let (loadout_section, paint_section) = if team == 1 {
+        ("Bot Loadout Orange", "Bot Paint Orange")
+    } else {
+        ("Bot Loadout", "Bot Paint Blue")
+    };
+
+    let mut loadout = PlayerLoadout::new();
+    if let Some(section) = ini
+        .section(loadout_section)
+        .or_else(|| ini.section("Bot Loadout"))
+    {
+        for entry in &section.entries {
+            loadout = match entry.key.as_str() {
+                // Older configs repeat the bot's name here. It is not used.
+                "name" => loadout,
+                "team_color_id" => loadout.team_color_id(ini.value(entry)?),
+                "custom_color_id" => loadout.custom_color_id(ini.value(entry)?),
+                "car_id" => loadout.car_id(ini.value(entry)?),
+                "decal_id" => loadout.decal_id(ini.value(entry)?),
+                "wheels_id" => loadout.wheels_id(ini.value(entry)?),
+                "boost_id" => loadout.boost_id(ini.value(entry)?),
+                "antenna_id" => loadout.antenna_id(ini.value(entry)?),
+                "hat_id" => loadout.hat_id(ini.value(entry)?),
+                "paint_finish_id" => loadout.paint_finish_id(ini.value(entry)?),
+                "custom_finish_id" => loadout.custom_finish_id(ini.value(entry)?),
+                "engine_audio_id" => loadout.engine_audio_id(ini.value(entry)?),
+                "trails_id" => loadout.trails_id(ini.value(entry)?),
+                "goal_explosion_id" => loadout.goal_explosion_id(ini.value(entry)?),
+                _ => return Err(ini.unknown(entry)),
+            };
+        }
+    }
+
+    let mut paint = LoadoutPaint::new();
+    if let Some(section) = ini.section(paint_section) {
+        for entry in &section.entries {
+            paint = match entry.key.as_str() {
+                "car_paint_id" => paint.car_paint_id(ini.value(entry)?),
+                "decal_paint_id" => paint.decal_paint_id(ini.value(entry)?),
+                "wheels_paint_id" => paint.wheels_paint_id(ini.value(entry)?),
+                "boost_paint_id" => paint.boost_paint_id(ini.value(entry)?),
+                "antenna_paint_id" => paint.antenna_paint_id(ini.value(entry)?),
+                "hat_paint_id" => paint.hat_paint_id(ini.value(entry)?),
+                "trails_paint_id" => paint.trails_paint_id(ini.value(entry)?),
+                "goal_explosion_paint_id" => paint.goal_explosion_paint_id(ini.value(entry)?),
+                _ => return Err(ini.unknown(entry)),
+            };
+        }
+    }
+
+    Ok(loadout.loadout_paint(paint))
+}
+
+/// A parsed INI file, in the dialect written by the framework's Python
+/// `configparser`: `[Section]` headers, `key = value` (or `key: value`) pairs,
+/// and whole-line `#` or `;` comments. Keys are case-insensitive, so they are
+/// stored lowercased.
+struct Ini {
+    path: PathBuf,
+    sections: Vec<Section>,
+}
+
+struct Section {
+    name: String,
+    entries: Vec<Entry>,
+}
+
+struct Entry {
+    key: String,
+    value: String,
+    line: usize,
+}
+
+impl Ini {
+    fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::Io {
+            path: path.to_owned(),
+            source,
+        })?;
+        Self::parse(path, &text)
+    }
+
+    fn parse(path: &Path, text: &str) -> Result<Self, ConfigError> {
+        let mut sections = Vec::<Section>::new();
+        for (i, text) in text.trim_start_matches('\u{feff}').lines().enumerate() {
+            let line = i + 1;
+            let trimmed = text.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') || trimmed.starts_with(';') {
+                continue;
+            }
+
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                sections.push(Section {
+                    name: trimmed[1..trimmed.len() - 1].trim().to_string(),
+                    entries: Vec::new(),
+                });
+                continue;
+            }
+
+            let separator = trimmed.find(['=', ':']);
+            match (separator, sections.last_mut()) {
+                (Some(separator), Some(section)) => section.entries.push(Entry {
+                    key: trimmed[..separator].trim().to_lowercase(),
+                    value: trimmed[separator + 1..].trim().to_string(),
+                    line,
+                }),
+                _ => {
+                    return Err(ConfigError::Syntax {
+                        path: path.to_owned(),
+                        line,
+                    })
+                }
+            }
+        }
+        Ok(Self {
+            path: path.to_owned(),
+            sections,
+        })
+    }
+
+    fn section(&self, name: &str) -> Option<&Section> {
+        self.sections.iter().find(|s| s.name == name)
+    }
+
+    fn value<T: FromStr>(&self, entry: &Entry) -> Result<T, ConfigError> {
+        entry.value.parse().map_err(|_| self.invalid(entry))
+    }
+
+    /// Parses a boolean the same way `configparser` does.
+    fn bool(&self, entry: &Entry) -> Result<bool, ConfigError> {
+        match entry.value.to_lowercase().as_str() {
+            "1" | "yes" | "true" | "on" => Ok(true),
+            "0" | "no" | "false" | "off" => Ok(false),
+            _ => Err(self.invalid(entry)),
+        }
+    }
+
+    fn lookup<T: Copy>(&self, entry: &Entry, table: &[(&str, T)]) -> Result<T, ConfigError> {
+        table
+            .iter()
+            .find(|&&(name, _)| name == entry.value)
+            .map(|&(_, value)| value)
+            .ok_or_else(|| self.invalid(entry))
+    }
+
+    fn invalid(&self, entry: &Entry) -> ConfigError {
+        ConfigError::InvalidValue {
+            path: self.path.clone(),
+            line: entry.line,
+            key: entry.key.clone(),
+            value: entry.value.clone(),
+        }
+    }
+
+    fn unknown(&self, entry: &Entry) -> ConfigError {
+        ConfigError::UnknownKey {
+            path: self.path.clone(),
+            line: entry.line,
+            key: entry.key.clone(),
+        }
+    }
+
+    fn missing(&self, section: &str, key: &str) -> ConfigError {
+        ConfigError::MissingKey {
+            path: self.path.clone(),
+            section: section.to_string(),
+            key: key.to_string(),
+        }
+    }
+}
+
+impl Section {
+    /// Returns the value of a key. If it appears more than once, the last
+    /// one wins, as in `configparser`.
+    fn get(&self, key: &str) -> Option<&str> {
+        self.entries
+            .iter()
+            .rev()
+            .find(|e| e.key == key)
+            .map(|e| e.value.as_str())
+    }
+}
+
+// The names below are the ones the framework writes, which are listed at
+// https://github.com/RLBot/RLBot/wiki/Config-File-Documentation
+
+const PARTICIPANT_KINDS: &[(&str, ParticipantKind)] = &[
+    ("human", ParticipantKind::Human),
+    ("rlbot", ParticipantKind::RLBot),
+    ("psyonix", ParticipantKind::Psyonix),
+    ("party_member_bot", ParticipantKind::PartyMember),
+];
+
+const EXISTING_MATCH_BEHAVIORS: &[(&str, ExistingMatchBehavior)] = &[
+    (
+        "Restart If Different",
+        ExistingMatchBehavior::Restart_If_Different,
+    ),
+    ("Restart", ExistingMatchBehavior::Restart),
+    (
+        "Continue And Spawn",
+        ExistingMatchBehavior::Continue_And_Spawn,
+    ),
+];
+
+const GAME_MODES: &[(&str, GameMode)] = &[
+    ("Soccer", GameMode::Soccer),
+    ("Hoops", GameMode::Hoops),
+    ("Dropshot", GameMode::Dropshot),
+    ("Hockey", GameMode::Hockey),
+    ("Rumble", GameMode::Rumble),
+];
+
+const GAME_MAPS: &[(&str, GameMap)] = &[
+    ("DFHStadium", GameMap::DFHStadium),
+    ("Mannfield", GameMap::Mannfield),
+    ("ChampionsField", GameMap::ChampionsField),
+    ("UrbanCentral", GameMap::UrbanCentral),
+    ("BeckwithPark", GameMap::BeckwithPark),
+    ("UtopiaColiseum", GameMap::UtopiaColiseum),
+    ("Wasteland", GameMap::Wasteland),
+    ("NeoTokyo", GameMap::NeoTokyo),
+    ("AquaDome", GameMap::AquaDome),
+    ("StarbaseArc", GameMap::StarbaseArc),
+    ("Farmstead", GameMap::Farmstead),
+    ("SaltyShores", GameMap::SaltyShores),
+    ("DFHStadium_Stormy", GameMap::DFHStadium_Stormy),
+    ("DFHStadium_Day", GameMap::DFHStadium_Day),
+    ("Mannfield_Stormy", GameMap::Mannfield_Stormy),
+    ("Mannfield_Night", GameMap::Mannfield_Night),
+    ("ChampionsField_Day", GameMap::ChampionsField_Day),
+    ("BeckwithPark_Stormy", GameMap::BeckwithPark_Stormy),
+    ("BeckwithPark_Midnight", GameMap::BeckwithPark_Midnight),
+    ("UrbanCentral_Night", GameMap::UrbanCentral_Night),
+    ("UrbanCentral_Dawn", GameMap::UrbanCentral_Dawn),
+    ("UtopiaColiseum_Dusk", GameMap::UtopiaColiseum_Dusk),
+    ("DFHStadium_Snowy", GameMap::DFHStadium_Snowy),
+    ("Mannfield_Snowy", GameMap::Mannfield_Snowy),
+    ("UtopiaColiseum_Snowy", GameMap::UtopiaColiseum_Snowy),
+    ("Badlands", GameMap::Badlands),
+    ("Badlands_Night", GameMap::Badlands_Night),
+    ("TokyoUnderpass", GameMap::TokyoUnderpass),
+    ("Arctagon", GameMap::Arctagon),
+    ("Pillars", GameMap::Pillars),
+    ("Cosmic", GameMap::Cosmic),
+    ("DoubleGoal", GameMap::DoubleGoal),
+    ("Octagon", GameMap::Octagon),
+    ("Underpass", GameMap::Underpass),
+    ("UtopiaRetro", GameMap::UtopiaRetro),
+    ("Hoops_DunkHouse", GameMap::Hoops_DunkHouse),
+    ("DropShot_Core707", GameMap::DropShot_Core707),
+    ("ThrowbackStadium", GameMap::ThrowbackStadium),
+];
+
+const MATCH_LENGTHS: &[(&str, MatchLength)] = &[
+    ("5 Minutes", MatchLength::Five_Minutes),
+    ("10 Minutes", MatchLength::Ten_Minutes),
+    ("20 Minutes", MatchLength::Twenty_Minutes),
+    ("Unlimited", MatchLength::Unlimited),
+];
+
+const MAX_SCORES: &[(&str, MaxScore)] = &[
+    ("Unlimited", MaxScore::Unlimited),
+    ("1 Goal", MaxScore::One_Goal),
+    ("3 Goals", MaxScore::Three_Goals),
+    ("5 Goals", MaxScore::Five_Goals),
+];
+
+const OVERTIMES: &[(&str, OvertimeOption)] = &[
+    ("Unlimited", OvertimeOption::Unlimited),
+    ("+5 Max, First Score", OvertimeOption::Five_Max_First_Score),
+    ("+5 Max, Random Team", OvertimeOption::Five_Max_Random_Team),
+];
+
+const SERIES_LENGTHS: &[(&str, SeriesLengthOption)] = &[
+    ("Unlimited", SeriesLengthOption::Unlimited),
+    ("3 Games", SeriesLengthOption::Three_Games),
+    ("5 Games", SeriesLengthOption::Five_Games),
+    ("7 Games", SeriesLengthOption::Seven_Games),
+];
+
+const GAME_SPEEDS: &[(&str, GameSpeedOption)] = &[
+    ("Default", GameSpeedOption::Default),
+    ("Slo-Mo", GameSpeedOption::Slo_Mo),
+    ("Time Warp", GameSpeedOption::Time_Warp),
+];
+
+const BALL_MAX_SPEEDS: &[(&str, BallMaxSpeedOption)] = &[
+    ("Default", BallMaxSpeedOption::Default),
+    ("Slow", BallMaxSpeedOption::Slow),
+    ("Fast", BallMaxSpeedOption::Fast),
+    ("Super Fast", BallMaxSpeedOption::Super_Fast),
+];
+
+const BALL_TYPES: &[(&str, BallTypeOption)] = &[
+    ("Default", BallTypeOption::Default),
+    ("Cube", BallTypeOption::Cube),
+    ("Puck", BallTypeOption::Puck),
+    ("Basketball", BallTypeOption::Basketball),
+];
+
+const BALL_WEIGHTS: &[(&str, BallWeightOption)] = &[
+    ("Default", BallWeightOption::Default),
+    ("Light", BallWeightOption::Light),
+    ("Heavy", BallWeightOption::Heavy),
+    ("Super Light", BallWeightOption::Super_Light),
+];
+
+const BALL_SIZES: &[(&str, BallSizeOption)] = &[
+    ("Default", BallSizeOption::Default),
+    ("Small", BallSizeOption::Small),
+    ("Large", BallSizeOption::Large),
+    ("Gigantic", BallSizeOption::Gigantic),
+];
+
+const BALL_BOUNCINESSES: &[(&str, BallBouncinessOption)] = &[
+    ("Default", BallBouncinessOption::Default),
+    ("Low", BallBouncinessOption::Low),
+    ("High", BallBouncinessOption::High),
+    ("Super High", BallBouncinessOption::Super_High),
+];
+
+const BOOST_AMOUNTS: &[(&str, BoostOption)] = &[
+    ("Default", BoostOption::Normal_Boost),
+    ("Unlimited", BoostOption::Unlimited_Boost),
+    ("Recharge (Slow)", BoostOption::Slow_Recharge),
+    ("Recharge (Fast)", BoostOption::Rapid_Recharge),
+    ("No Boost", BoostOption::No_Boost),
+];
+
+const RUMBLES: &[(&str, RumbleOption)] = &[
+    ("None", RumbleOption::No_Rumble),
+    ("Default", RumbleOption::Default),
+    ("Slow", RumbleOption::Slow),
+    ("Civilized", RumbleOption::Civilized),
+    ("Destruction Derby", RumbleOption::Destruction_Derby),
+    ("Spring Loaded", RumbleOption::Spring_Loaded),
+    ("Spikes Only", RumbleOption::Spikes_Only),
+    ("Spike Rush", RumbleOption::Spike_Rush),
+];
+
+const BOOST_STRENGTHS: &[(&str, BoostStrengthOption)] = &[
+    ("1x", BoostStrengthOption::One),
+    ("1.5x", BoostStrengthOption::OneAndAHalf),
+    ("2x", BoostStrengthOption::Two),
+    ("10x", BoostStrengthOption::Ten),
+];
+
+const GRAVITIES: &[(&str, GravityOption)] = &[
+    ("Default", GravityOption::Default),
+    ("Low", GravityOption::Low),
+    ("High", GravityOption::High),
+    ("Super High", GravityOption::Super_High),
+];
+
+const DEMOLISHES: &[(&str, DemolishOption)] = &[
+    ("Default", DemolishOption::Default),
+    ("Disabled", DemolishOption::Disabled),
+    ("Friendly Fire", DemolishOption::Friendly_Fire),
+    ("On Contact", DemolishOption::On_Contact),
+    ("On Contact (FF)", DemolishOption::On_Contact_FF),
+];
+
+const RESPAWN_TIMES: &[(&str, RespawnTimeOption)] = &[
+    ("3 Seconds", RespawnTimeOption::Three_Seconds),
+    ("2 Seconds", RespawnTimeOption::Two_Seconds),
+    ("1 Second", RespawnTimeOption::One_Seconds),
+    ("Disable Goal Reset", RespawnTimeOption::Disable_Goal_Reset),
+];
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        error::ConfigError,
+        match_config::{load, mutator_settings, Ini},
+        match_settings::{GameMap, GameSpeedOption, MatchLength, PlayerClass, RumbleOption},
+    };
+    use std::path::Path;
+
+    fn parse(text: &str) -> Ini {
+        Ini::parse(Path::new("test.cfg"), text).unwrap()
+    }
+
+    #[test]
+    fn parses_configparser_syntax() {
+        let ini = parse(
+            "# comment\n[Section]\nKey = value with spaces\nother: x\n; another\n\n[Empty]\n",
+        );
+        let section = ini.section("Section").unwrap();
+        assert_eq!(section.get("key"), Some("value with spaces"));
+        assert_eq!(section.get("other"), Some("x"));
+        assert_eq!(section.entries[1].line, 4);
+        assert!(ini.section("Empty").unwrap().entries.is_empty());
+    }
+
+    #[test]
+    fn reports_syntax_errors_with_line_numbers() {
+        match Ini::parse(Path::new("test.cfg"), "[Section]\na = 1\noops\n") {
+            Err(ConfigError::Syntax { line, .. }) => assert_eq!(line, 3),
+            _ => panic!("expected a syntax error"),
+        }
+        // Keys must be inside a section.
+        match Ini::parse(Path::new("test.cfg"), "a = 1\n") {
+            Err(ConfigError::Syntax { line, .. }) => assert_eq!(line, 1),
+            _ => panic!("expected a syntax error"),
+        }
+    }
+
+    #[test]
+    fn maps_mutators() {
+        let ini = parse(
+            "[Mutator Configuration]\nMatch Length = Unlimited\nGame Speed = Slo-Mo\nRumble = \
+             Spike Rush\n",
+        );
+        let mutators = mutator_settings(&ini, ini.section("Mutator Configuration").unwrap());
+        let mutators = mutators.unwrap();
+        assert_eq!(mutators.match_length, MatchLength::Unlimited);
+        assert_eq!(mutators.game_speed_option, GameSpeedOption::Slo_Mo);
+        assert_eq!(mutators.rumble_option, RumbleOption::Spike_Rush);
+    }
+
+    #[test]
+    fn reports_unknown_keys_and_values() {
+        let ini = parse("[Mutator Configuration]\n\nGravity = Sideways\n");
+        match mutator_settings(&ini, ini.section("Mutator Configuration").unwrap()) {
+            Err(ConfigError::InvalidValue {
+                line, key, value, ..
+            }) => {
+                assert_eq!(line, 3);
+                assert_eq!(key, "gravity");
+                assert_eq!(value, "Sideways");
+            }
+            _ => panic!("expected an invalid value"),
+        }
+
+        let ini = parse("[Mutator Configuration]\nFun Level = 11\n");
+        match mutator_settings(&ini, ini.section("Mutator Configuration").unwrap()) {
+            Err(ConfigError::UnknownKey { line, key, .. }) => {
+                assert_eq!((line, key.as_str()), (2, "fun level"));
+            }
+            _ => panic!("expected an unknown key"),
+        }
+    }
+
+    #[test]
+    fn loads_the_repo_config() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("rlbot.cfg");
+        let settings = load(&path).unwrap();
+        assert_eq!(settings.game_map, GameMap::Mannfield);
+        assert_eq!(settings.player_configurations.len(), 2);
+        let orange = &settings.player_configurations[1];
+        assert_eq!(orange.name, "ATBA");
+        assert_eq!(orange.team, 1);
+        match orange.variety {
+            PlayerClass::RLBotPlayer => {}
+            _ => panic!("expected an RLBot player"),
+        }
+    }
+
+    #[test]
+    fn accepts_framework_only_keys() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/configs/framework_keys.cfg");
+        let settings = load(&path).unwrap();
+        assert_eq!(settings.game_map, GameMap::DFHStadium);
+        assert_eq!(settings.player_configurations.len(), 1);
+    }
+}
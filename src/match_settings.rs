@@ -2,17 +2,18 @@
 
 pub use crate::flat::{
     BallBouncinessOption, BallMaxSpeedOption, BallSizeOption, BallTypeOption, BallWeightOption,
-    BoostOption, DemolishOption, GameMap, GameMode, GameSpeedOption, GravityOption, MatchLength,
-    MaxScore, OvertimeOption, RespawnTimeOption, RumbleOption, SeriesLengthOption,
-};
-use crate::{
-    flat,
-    rlbot_generated::rlbot::flat::{BoostStrengthOption, ExistingMatchBehavior},
+    BoostOption, DemolishOption, ExistingMatchBehavior, GameMap, GameMode, GameSpeedOption,
+    GravityOption, MatchLength, MaxScore, OvertimeOption, RespawnTimeOption, RumbleOption,
+    SeriesLengthOption,
 };
+use crate::{error::ConfigError, flat, rlbot_generated::rlbot::flat::BoostStrengthOption};
 use flatbuffers::{FlatBufferBuilder, UnionWIPOffset, WIPOffset};
+use std::path::Path;
 
 /// A psyonix bot, e.g. All Star bot
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PsyonixBotPlayer {
     /// The skill of the bot, from 0.0 (Rookie) to 1.0 (All-star).
     pub bot_skill: f32,
@@ -40,7 +41,8 @@ impl PsyonixBotPlayer {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PlayerClass {
     /// A bot controlled by the RLBot framework
     RLBotPlayer,
@@ -87,7 +89,9 @@ impl PlayerClass {
 
 /// The car type, color, and other aspects of the player's appearance.
 /// See https://github.com/RLBot/RLBot/wiki/Bot-Customization
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct PlayerLoadout {
     team_color_id: i32,
     custom_color_id: i32,
@@ -205,7 +209,9 @@ impl PlayerLoadout {
 }
 
 /// Specification for 'painted' items. See https://github.com/RLBot/RLBot/wiki/Bot-Customization
-#[derive(Clone, Default)]
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct LoadoutPaint {
     car_paint_id: i32,
     decal_paint_id: i32,
@@ -281,20 +287,23 @@ impl LoadoutPaint {
 }
 
 /// Describes one of the players in a match.
-#[derive(Clone)]
-pub struct PlayerConfiguration<'a> {
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerConfiguration {
     pub variety: PlayerClass,
-    pub name: &'a str,
+    pub name: String,
     pub team: i32,
+    #[cfg_attr(feature = "serde", serde(default))]
     pub loadout: PlayerLoadout,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _non_exhaustive: (),
 }
 
-impl<'a> PlayerConfiguration<'a> {
-    pub fn new(variety: PlayerClass, name: &'a str, team: i32) -> Self {
+impl PlayerConfiguration {
+    pub fn new(variety: PlayerClass, name: impl Into<String>, team: i32) -> Self {
         Self {
             variety,
-            name,
+            name: name.into(),
             team,
             loadout: PlayerLoadout::default(),
             _non_exhaustive: (),
@@ -306,8 +315,8 @@ impl<'a> PlayerConfiguration<'a> {
         self
     }
 
-    pub fn name(mut self, name: &'a str) -> Self {
-        self.name = name;
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = name.into();
         self
     }
 
@@ -329,7 +338,7 @@ impl<'a> PlayerConfiguration<'a> {
         let args = flat::PlayerConfigurationArgs {
             variety_type,
             variety: Some(variety),
-            name: Some(builder.create_string(self.name)),
+            name: Some(builder.create_string(&self.name)),
             team: self.team,
             loadout: Some(self.loadout.build(builder)),
         };
@@ -337,7 +346,9 @@ impl<'a> PlayerConfiguration<'a> {
     }
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct MutatorSettings {
     pub match_length: MatchLength,
     pub max_score: MaxScore,
@@ -355,6 +366,7 @@ pub struct MutatorSettings {
     pub gravity_option: GravityOption,
     pub demolish_option: DemolishOption,
     pub respawn_time_option: RespawnTimeOption,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _non_exhaustive: (),
 }
 
@@ -497,18 +509,26 @@ impl MutatorSettings {
 ///
 /// Pass this to [`RLBot::start_match`](crate::RLBot::start_match)` to make the
 /// magic happen.
-#[derive(Clone)]
-pub struct MatchSettings<'a> {
-    pub player_configurations: Vec<PlayerConfiguration<'a>>,
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct MatchSettings {
+    pub player_configurations: Vec<PlayerConfiguration>,
     pub game_mode: GameMode,
     pub game_map: GameMap,
     pub skip_replays: bool,
     pub instant_start: bool,
     pub mutator_settings: MutatorSettings,
+    /// What to do if a match is already running when this one is started.
+    pub existing_match_behavior: ExistingMatchBehavior,
+    /// If true, the game waits for every bot to respond before advancing to
+    /// the next frame.
+    pub enable_lockstep: bool,
+    #[cfg_attr(feature = "serde", serde(skip))]
     _non_exhaustive: (),
 }
 
-impl<'a> Default for MatchSettings<'a> {
+impl Default for MatchSettings {
     fn default() -> Self {
         Self {
             player_configurations: Vec::new(),
@@ -517,18 +537,20 @@ impl<'a> Default for MatchSettings<'a> {
             skip_replays: false,
             instant_start: false,
             mutator_settings: Default::default(),
+            existing_match_behavior: ExistingMatchBehavior::Restart_If_Different,
+            enable_lockstep: false,
             _non_exhaustive: (),
         }
     }
 }
 
-impl<'a> MatchSettings<'a> {
+impl MatchSettings {
     pub fn new() -> Self {
         Self::default()
     }
 
     /// Create a `MatchSettings` for a 1v1 game with two RLBot-controlled bots.
-    pub fn rlbot_vs_rlbot(blue_name: &'a str, orange_name: &'a str) -> Self {
+    pub fn rlbot_vs_rlbot(blue_name: impl Into<String>, orange_name: impl Into<String>) -> Self {
         Self::new().player_configurations(vec![
             PlayerConfiguration::new(PlayerClass::RLBotPlayer, blue_name, 0),
             PlayerConfiguration::new(PlayerClass::RLBotPlayer, orange_name, 1),
@@ -537,7 +559,10 @@ impl<'a> MatchSettings<'a> {
 
     /// Create a `MatchSettings` for a 1v1 game with Team Blue as an
     /// RLBot-controlled bot, and Team Orange as a Psyonix all-star bot.
-    pub fn rlbot_vs_allstar(rlbot_name: &'a str, allstar_name: &'a str) -> Self {
+    pub fn rlbot_vs_allstar(
+        rlbot_name: impl Into<String>,
+        allstar_name: impl Into<String>,
+    ) -> Self {
         Self::new().player_configurations(vec![
             PlayerConfiguration::new(PlayerClass::RLBotPlayer, rlbot_name, 0),
             PlayerConfiguration::new(PlayerClass::psyonix_bot(1.0), allstar_name, 1),
@@ -545,16 +570,29 @@ impl<'a> MatchSettings<'a> {
     }
 
     /// Create a `MatchSettings` for a 1v1 game with two Psyonix all-star bots.
-    pub fn allstar_vs_allstar(blue_name: &'a str, orange_name: &'a str) -> Self {
+    pub fn allstar_vs_allstar(
+        blue_name: impl Into<String>,
+        orange_name: impl Into<String>,
+    ) -> Self {
         Self::new().player_configurations(vec![
             PlayerConfiguration::new(PlayerClass::psyonix_bot(1.0), blue_name, 0),
             PlayerConfiguration::new(PlayerClass::psyonix_bot(1.0), orange_name, 1),
         ])
     }
 
+    /// Loads a `MatchSettings` from an `rlbot.cfg` file, in the format used by
+    /// the RLBot framework.
+    ///
+    /// Participant configs and loadouts are resolved relative to the file
+    /// that names them. Unknown keys and values are reported as errors, along
+    /// with their line numbers.
+    pub fn from_config_file(path: impl AsRef<Path>) -> Result<Self, ConfigError> {
+        crate::match_config::load(path.as_ref())
+    }
+
     pub fn player_configurations(
         mut self,
-        player_configurations: impl IntoIterator<Item = PlayerConfiguration<'a>>,
+        player_configurations: impl IntoIterator<Item = PlayerConfiguration>,
     ) -> Self {
         self.player_configurations = player_configurations.into_iter().collect();
         self
@@ -585,6 +623,23 @@ impl<'a> MatchSettings<'a> {
         self
     }
 
+    /// Sets what to do if a match is already running. The default is
+    /// [`Restart_If_Different`](ExistingMatchBehavior::Restart_If_Different).
+    pub fn existing_match_behavior(
+        mut self,
+        existing_match_behavior: ExistingMatchBehavior,
+    ) -> Self {
+        self.existing_match_behavior = existing_match_behavior;
+        self
+    }
+
+    /// Enables or disables lockstep mode, where the game waits for every bot
+    /// to respond before advancing to the next frame.
+    pub fn enable_lockstep(mut self, enable_lockstep: bool) -> Self {
+        self.enable_lockstep = enable_lockstep;
+        self
+    }
+
     pub(crate) fn build(&self) -> FlatBufferBuilder<'_> {
         let mut builder = FlatBufferBuilder::new_with_capacity(1024);
 
@@ -597,11 +652,11 @@ impl<'a> MatchSettings<'a> {
             playerConfigurations: Some(builder.create_vector(&player_configurations)),
             gameMode: self.game_mode,
             gameMap: self.game_map,
-            skipReplays: false,
-            instantStart: false,
+            skipReplays: self.skip_replays,
+            instantStart: self.instant_start,
             mutatorSettings: Some(self.mutator_settings.build(&mut builder)),
-            existingMatchBehavior: ExistingMatchBehavior::Restart_If_Different,
-            enableLockstep: false,
+            existingMatchBehavior: self.existing_match_behavior,
+            enableLockstep: self.enable_lockstep,
         };
         let root = flat::MatchSettings::create(&mut builder, &args);
 
@@ -609,3 +664,68 @@ impl<'a> MatchSettings<'a> {
         builder
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::{flat, match_settings::*};
+
+    fn round_trip(settings: &MatchSettings, f: impl FnOnce(flat::MatchSettings<'_>)) {
+        let builder = settings.build();
+        f(flatbuffers::get_root::<flat::MatchSettings<'_>>(
+            builder.finished_data(),
+        ));
+    }
+
+    #[test]
+    fn defaults_round_trip() {
+        round_trip(&MatchSettings::new(), |settings| {
+            assert!(!settings.skipReplays());
+            assert!(!settings.instantStart());
+            assert_eq!(
+                settings.existingMatchBehavior(),
+                ExistingMatchBehavior::Restart_If_Different,
+            );
+            assert!(!settings.enableLockstep());
+        });
+    }
+
+    #[test]
+    fn flags_round_trip() {
+        let settings = MatchSettings::rlbot_vs_allstar("Chell", "Wheatley")
+            .skip_replays(true)
+            .instant_start(true)
+            .existing_match_behavior(ExistingMatchBehavior::Continue_And_Spawn)
+            .enable_lockstep(true);
+        round_trip(&settings, |settings| {
+            assert!(settings.skipReplays());
+            assert!(settings.instantStart());
+            assert_eq!(
+                settings.existingMatchBehavior(),
+                ExistingMatchBehavior::Continue_And_Spawn,
+            );
+            assert!(settings.enableLockstep());
+            assert_eq!(settings.playerConfigurations().unwrap().len(), 2);
+        });
+
+        let settings = MatchSettings::new().existing_match_behavior(ExistingMatchBehavior::Restart);
+        round_trip(&settings, |settings| {
+            assert_eq!(
+                settings.existingMatchBehavior(),
+                ExistingMatchBehavior::Restart,
+            );
+        });
+    }
+
+    #[test]
+    fn owned_names() {
+        fn assert_send_static<T: Send + 'static>(_: &T) {}
+
+        let names = vec![String::from("Chell"), String::from("Wheatley")];
+        let settings = MatchSettings::new().player_configurations(names.iter().enumerate().map(
+            |(i, name)| PlayerConfiguration::new(PlayerClass::RLBotPlayer, &**name, i as i32),
+        ));
+        drop(names);
+        assert_send_static(&settings);
+        assert_eq!(settings, MatchSettings::rlbot_vs_rlbot("Chell", "Wheatley"));
+    }
+}
@@ -0,0 +1,495 @@
+//! Vector math on the game's linear algebra types.
+//!
+//! Rocket League's world uses a left-handed coordinate system: from the blue
+//! team's point of view, `x` points left, `y` points towards the orange goal,
+//! and `z` points up. A positive yaw turns a car to its right (clockwise when
+//! viewed from above), a positive pitch raises its nose, and a positive roll
+//! tilts it to its right.
+//!
+//! [`RotationMatrix`] and [`Quaternion`] follow the same conventions as the
+//! [`Rotator`] the game reports, so converting between any of the three always
+//! describes the same orientation.
+
+#![allow(clippy::float_cmp)]
+
+use crate::{
+    game::{Quaternion, Rotator, Vector3},
+    state::{RotatorPartial, Vector3Partial},
+    state_convert::{FromPoint3, FromVector3},
+};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+impl Vector3 {
+    /// The vector with all components zero.
+    pub const ZERO: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+    };
+
+    /// Constructs a new `Vector3`.
+    pub fn new(x: f32, y: f32, z: f32) -> Self {
+        Self { x, y, z }
+    }
+
+    /// Returns the dot product of two vectors.
+    pub fn dot(self, other: Self) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    /// Returns the cross product of two vectors.
+    ///
+    /// Because the world is left-handed, `forward.cross(right)` is `up`.
+    pub fn cross(self, other: Self) -> Self {
+        Self {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Returns the length of the vector.
+    pub fn length(self) -> f32 {
+        self.length_squared().sqrt()
+    }
+
+    /// Returns the squared length of the vector. This is cheaper than
+    /// [`length`](Vector3::length), and is good enough for comparisons.
+    pub fn length_squared(self) -> f32 {
+        self.dot(self)
+    }
+
+    /// Returns the distance between two points.
+    pub fn distance(self, other: Self) -> f32 {
+        (other - self).length()
+    }
+
+    /// Returns a vector pointing in the same direction with a length of 1.
+    ///
+    /// The zero vector has no direction, so it is returned unchanged.
+    pub fn normalize(self) -> Self {
+        let length = self.length();
+        if length == 0.0 {
+            self
+        } else {
+            self / length
+        }
+    }
+}
+
+impl Add for Vector3 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl Sub for Vector3 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl Mul<f32> for Vector3 {
+    type Output = Self;
+
+    fn mul(self, scale: f32) -> Self {
+        Self::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+impl Mul<Vector3> for f32 {
+    type Output = Vector3;
+
+    fn mul(self, vector: Vector3) -> Vector3 {
+        vector * self
+    }
+}
+
+impl Div<f32> for Vector3 {
+    type Output = Self;
+
+    fn div(self, scale: f32) -> Self {
+        Self::new(self.x / scale, self.y / scale, self.z / scale)
+    }
+}
+
+impl Neg for Vector3 {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z)
+    }
+}
+
+impl AddAssign for Vector3 {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl SubAssign for Vector3 {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl MulAssign<f32> for Vector3 {
+    fn mul_assign(&mut self, scale: f32) {
+        *self = *self * scale;
+    }
+}
+
+impl DivAssign<f32> for Vector3 {
+    fn div_assign(&mut self, scale: f32) {
+        *self = *self / scale;
+    }
+}
+
+impl Rotator {
+    /// Constructs a new `Rotator`.
+    pub fn new(pitch: f32, yaw: f32, roll: f32) -> Self {
+        Self { pitch, yaw, roll }
+    }
+}
+
+impl Quaternion {
+    /// The quaternion which represents no rotation.
+    pub const IDENTITY: Self = Self {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// Constructs a new `Quaternion`.
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Returns the quaternion which undoes this rotation. This assumes the
+    /// quaternion is normalized.
+    pub fn conjugate(self) -> Self {
+        Self::new(-self.x, -self.y, -self.z, self.w)
+    }
+
+    /// Returns the quaternion scaled to a length of 1.
+    ///
+    /// The zero quaternion is not a rotation, so it is normalized to
+    /// [`IDENTITY`](Quaternion::IDENTITY).
+    pub fn normalize(self) -> Self {
+        let length = (self.x * self.x + self.y * self.y + self.z * self.z + self.w * self.w).sqrt();
+        if length == 0.0 {
+            return Self::IDENTITY;
+        }
+        Self::new(
+            self.x / length,
+            self.y / length,
+            self.z / length,
+            self.w / length,
+        )
+    }
+}
+
+/// Composes two rotations. The result applies `other` first, then `self`.
+impl Mul for Quaternion {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            x: self.w * other.x + self.x * other.w + self.y * other.z - self.z * other.y,
+            y: self.w * other.y - self.x * other.z + self.y * other.w + self.z * other.x,
+            z: self.w * other.z + self.x * other.y - self.y * other.x + self.z * other.w,
+            w: self.w * other.w - self.x * other.x - self.y * other.y - self.z * other.z,
+        }
+    }
+}
+
+/// Rotates a vector, i.e. transforms it from an object's local frame into the
+/// world frame.
+impl Mul<Vector3> for Quaternion {
+    type Output = Vector3;
+
+    fn mul(self, v: Vector3) -> Vector3 {
+        let q = Vector3::new(self.x, self.y, self.z);
+        let t = 2.0 * q.cross(v);
+        v + self.w * t + q.cross(t)
+    }
+}
+
+/// An orientation, stored as the directions of an object's local axes in world
+/// coordinates.
+///
+/// Read as a matrix, these are the columns, so multiplying by a vector
+/// transforms it from the object's local frame into the world frame.
+///
+/// Rocket League's world is left-handed: from the blue team's point of view,
+/// `x` points left, `y` points towards the orange goal, and `z` points up. A
+/// positive yaw turns an object to its right (from `+x` towards `+y`), a
+/// positive pitch raises its nose, and a positive roll tilts it to its right.
+/// [`Rotator`], [`Quaternion`] and `RotationMatrix` all follow these
+/// conventions, so converting between them preserves the orientation.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct RotationMatrix {
+    /// The direction the object's nose points.
+    pub forward: Vector3,
+    /// The direction of the object's right side.
+    pub right: Vector3,
+    /// The direction of the object's roof.
+    pub up: Vector3,
+}
+
+impl RotationMatrix {
+    /// The matrix which represents no rotation.
+    pub const IDENTITY: Self = Self {
+        forward: Vector3 {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        },
+        right: Vector3 {
+            x: 0.0,
+            y: 1.0,
+            z: 0.0,
+        },
+        up: Vector3 {
+            x: 0.0,
+            y: 0.0,
+            z: 1.0,
+        },
+    };
+
+    /// Returns the inverse rotation, which transforms world coordinates into
+    /// the object's local frame.
+    pub fn transpose(self) -> Self {
+        let Self { forward, right, up } = self;
+        Self {
+            forward: Vector3::new(forward.x, right.x, up.x),
+            right: Vector3::new(forward.y, right.y, up.y),
+            up: Vector3::new(forward.z, right.z, up.z),
+        }
+    }
+}
+
+impl Mul<Vector3> for RotationMatrix {
+    type Output = Vector3;
+
+    fn mul(self, v: Vector3) -> Vector3 {
+        self.forward * v.x + self.right * v.y + self.up * v.z
+    }
+}
+
+/// Composes two rotations. The result applies `other` first, then `self`.
+impl Mul for RotationMatrix {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        Self {
+            forward: self * other.forward,
+            right: self * other.right,
+            up: self * other.up,
+        }
+    }
+}
+
+impl From<Rotator> for RotationMatrix {
+    fn from(rotator: Rotator) -> Self {
+        let (sp, cp) = rotator.pitch.sin_cos();
+        let (sy, cy) = rotator.yaw.sin_cos();
+        let (sr, cr) = rotator.roll.sin_cos();
+        Self {
+            forward: Vector3::new(cp * cy, cp * sy, sp),
+            right: Vector3::new(cy * sp * sr - cr * sy, sy * sp * sr + cr * cy, -cp * sr),
+            up: Vector3::new(-cr * cy * sp - sr * sy, -cr * sy * sp + sr * cy, cp * cr),
+        }
+    }
+}
+
+impl From<RotationMatrix> for Rotator {
+    fn from(m: RotationMatrix) -> Self {
+        Self {
+            pitch: m.forward.z.clamp(-1.0, 1.0).asin(),
+            yaw: m.forward.y.atan2(m.forward.x),
+            roll: (-m.right.z).atan2(m.up.z),
+        }
+    }
+}
+
+impl From<Quaternion> for RotationMatrix {
+    fn from(q: Quaternion) -> Self {
+        let Quaternion { x, y, z, w } = q;
+        Self {
+            forward: Vector3::new(
+                1.0 - 2.0 * (y * y + z * z),
+                2.0 * (x * y + z * w),
+                2.0 * (x * z - y * w),
+            ),
+            right: Vector3::new(
+                2.0 * (x * y - z * w),
+                1.0 - 2.0 * (x * x + z * z),
+                2.0 * (y * z + x * w),
+            ),
+            up: Vector3::new(
+                2.0 * (x * z + y * w),
+                2.0 * (y * z - x * w),
+                1.0 - 2.0 * (x * x + y * y),
+            ),
+        }
+    }
+}
+
+impl From<RotationMatrix> for Quaternion {
+    fn from(m: RotationMatrix) -> Self {
+        let (f, r, u) = (m.forward, m.right, m.up);
+        let trace = f.x + r.y + u.z;
+        // Branch on the largest diagonal element to keep the square root well
+        // away from zero.
+        let q = if trace > 0.0 {
+            let s = 2.0 * (trace + 1.0).sqrt();
+            Self::new((r.z - u.y) / s, (u.x - f.z) / s, (f.y - r.x) / s, s / 4.0)
+        } else if f.x > r.y && f.x > u.z {
+            let s = 2.0 * (1.0 + f.x - r.y - u.z).sqrt();
+            Self::new(s / 4.0, (r.x + f.y) / s, (u.x + f.z) / s, (r.z - u.y) / s)
+        } else if r.y > u.z {
+            let s = 2.0 * (1.0 + r.y - f.x - u.z).sqrt();
+            Self::new((r.x + f.y) / s, s / 4.0, (u.y + r.z) / s, (u.x - f.z) / s)
+        } else {
+            let s = 2.0 * (1.0 + u.z - f.x - r.y).sqrt();
+            Self::new((u.x + f.z) / s, (u.y + r.z) / s, s / 4.0, (f.y - r.x) / s)
+        };
+        q.normalize()
+    }
+}
+
+impl From<Rotator> for Quaternion {
+    fn from(rotator: Rotator) -> Self {
+        <RotationMatrix as From<_>>::from(rotator).into()
+    }
+}
+
+impl From<Quaternion> for Rotator {
+    fn from(quaternion: Quaternion) -> Self {
+        <RotationMatrix as From<_>>::from(quaternion).into()
+    }
+}
+
+impl FromVector3<Vector3> for Vector3Partial {
+    fn from(v: Vector3) -> Self {
+        Self::new().x(v.x).y(v.y).z(v.z)
+    }
+}
+
+impl FromPoint3<Vector3> for Vector3Partial {
+    fn from(v: Vector3) -> Self {
+        Self::new().x(v.x).y(v.y).z(v.z)
+    }
+}
+
+impl From<Rotator> for RotatorPartial {
+    fn from(r: Rotator) -> Self {
+        Self::new().pitch(r.pitch).yaw(r.yaw).roll(r.roll)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        game::{Quaternion, Rotator, Vector3},
+        math::RotationMatrix,
+    };
+    use std::f32::consts::PI;
+
+    fn assert_near(a: Vector3, b: Vector3) {
+        assert!((a - b).length() < 1e-5, "{:?} != {:?}", a, b);
+    }
+
+    fn assert_same_orientation(a: Rotator, b: Rotator) {
+        let (a, b) = (RotationMatrix::from(a), RotationMatrix::from(b));
+        assert_near(a.forward, b.forward);
+        assert_near(a.right, b.right);
+        assert_near(a.up, b.up);
+    }
+
+    #[test]
+    fn vector_ops() {
+        let a = Vector3::new(1.0, 2.0, 3.0);
+        let b = Vector3::new(4.0, 5.0, 6.0);
+        assert_eq!(a + b, Vector3::new(5.0, 7.0, 9.0));
+        assert_eq!(b - a, Vector3::new(3.0, 3.0, 3.0));
+        assert_eq!(2.0 * a, a * 2.0);
+        assert_eq!(-a / 1.0, Vector3::new(-1.0, -2.0, -3.0));
+        assert_eq!(a.dot(b), 32.0);
+        assert_eq!(a.cross(b), Vector3::new(-3.0, 6.0, -3.0));
+        assert_eq!(Vector3::new(3.0, 4.0, 0.0).length(), 5.0);
+        assert_eq!(
+            Vector3::new(0.0, 0.0, 2.0).normalize(),
+            Vector3::new(0.0, 0.0, 1.0)
+        );
+        assert_eq!(Vector3::ZERO.normalize(), Vector3::ZERO);
+    }
+
+    #[test]
+    fn positive_yaw_turns_right() {
+        // Starting from +x (the blue team's left), a quarter turn to the right
+        // should face +y (towards the orange goal).
+        let m = RotationMatrix::from(Rotator::new(0.0, PI / 2.0, 0.0));
+        assert_near(m.forward, Vector3::new(0.0, 1.0, 0.0));
+        assert_near(m.right, Vector3::new(-1.0, 0.0, 0.0));
+        assert_near(m.up, Vector3::new(0.0, 0.0, 1.0));
+        assert_near(m.forward.cross(m.right), m.up);
+    }
+
+    #[test]
+    fn pitch_and_roll_directions() {
+        let m = RotationMatrix::from(Rotator::new(PI / 2.0, 0.0, 0.0));
+        assert_near(m.forward, Vector3::new(0.0, 0.0, 1.0));
+
+        let m = RotationMatrix::from(Rotator::new(0.0, 0.0, PI / 2.0));
+        assert_near(m.right, Vector3::new(0.0, 0.0, -1.0));
+        assert_near(m.up, Vector3::new(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn rotator_round_trips() {
+        for &(pitch, yaw, roll) in &[
+            (0.0, 0.0, 0.0),
+            (0.3, -2.0, 1.0),
+            (-1.2, 3.0, -2.5),
+            (1.0, 0.5, 3.1),
+            (0.0, PI, 0.0),
+        ] {
+            let rotator = Rotator::new(pitch, yaw, roll);
+            assert_same_orientation(RotationMatrix::from(rotator).into(), rotator);
+            assert_same_orientation(Quaternion::from(rotator).into(), rotator);
+        }
+    }
+
+    #[test]
+    fn quaternion_matches_matrix() {
+        let rotator = Rotator::new(0.4, -1.1, 2.2);
+        let m = RotationMatrix::from(rotator);
+        let q = Quaternion::from(rotator);
+        let v = Vector3::new(1.0, -2.0, 0.5);
+        assert_near(q * v, m * v);
+        assert_near(m.transpose() * (m * v), v);
+        assert_near(q.conjugate() * (q * v), v);
+
+        let other = Rotator::new(-0.2, 0.7, 0.1);
+        let composed = RotationMatrix::from(q * Quaternion::from(other));
+        let expected = m * RotationMatrix::from(other);
+        assert_near(composed.forward, expected.forward);
+        assert_near(composed.right, expected.right);
+        assert_near(composed.up, expected.up);
+    }
+
+    #[test]
+    fn quaternion_normalize() {
+        let q = Quaternion::new(0.0, 0.0, 3.0, 4.0).normalize();
+        assert_eq!(q, Quaternion::new(0.0, 0.0, 0.6, 0.8));
+        let zero = Quaternion::new(0.0, 0.0, 0.0, 0.0);
+        assert_eq!(zero.normalize(), Quaternion::IDENTITY);
+    }
+}
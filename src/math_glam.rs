@@ -0,0 +1,44 @@
+use crate::{
+    game::{Quaternion, Vector3},
+    math::RotationMatrix,
+};
+
+impl From<Vector3> for glam::Vec3 {
+    fn from(v: Vector3) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<glam::Vec3> for Vector3 {
+    fn from(v: glam::Vec3) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Quaternion> for glam::Quat {
+    fn from(q: Quaternion) -> Self {
+        Self::from_xyzw(q.x, q.y, q.z, q.w)
+    }
+}
+
+impl From<glam::Quat> for Quaternion {
+    fn from(q: glam::Quat) -> Self {
+        Self::new(q.x, q.y, q.z, q.w)
+    }
+}
+
+impl From<RotationMatrix> for glam::Mat3 {
+    fn from(m: RotationMatrix) -> Self {
+        Self::from_cols(m.forward.into(), m.right.into(), m.up.into())
+    }
+}
+
+impl From<glam::Mat3> for RotationMatrix {
+    fn from(m: glam::Mat3) -> Self {
+        Self {
+            forward: m.x_axis.into(),
+            right: m.y_axis.into(),
+            up: m.z_axis.into(),
+        }
+    }
+}
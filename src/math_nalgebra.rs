@@ -0,0 +1,88 @@
+use crate::{
+    game::{Quaternion, Vector3},
+    math::RotationMatrix,
+};
+
+impl From<Vector3> for na::Vector3<f32> {
+    fn from(v: Vector3) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<na::Vector3<f32>> for Vector3 {
+    fn from(v: na::Vector3<f32>) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<Vector3> for na::Point3<f32> {
+    fn from(v: Vector3) -> Self {
+        Self::new(v.x, v.y, v.z)
+    }
+}
+
+impl From<na::Point3<f32>> for Vector3 {
+    fn from(p: na::Point3<f32>) -> Self {
+        Self::new(p.x, p.y, p.z)
+    }
+}
+
+impl From<Quaternion> for na::UnitQuaternion<f32> {
+    fn from(q: Quaternion) -> Self {
+        Self::from_quaternion(na::Quaternion::new(q.w, q.x, q.y, q.z))
+    }
+}
+
+impl From<na::UnitQuaternion<f32>> for Quaternion {
+    fn from(q: na::UnitQuaternion<f32>) -> Self {
+        let c = &q.quaternion().coords;
+        Self::new(c.x, c.y, c.z, c.w)
+    }
+}
+
+impl From<RotationMatrix> for na::Rotation3<f32> {
+    fn from(m: RotationMatrix) -> Self {
+        Self::from_matrix_unchecked(na::Matrix3::from_columns(&[
+            m.forward.into(),
+            m.right.into(),
+            m.up.into(),
+        ]))
+    }
+}
+
+impl From<na::Rotation3<f32>> for RotationMatrix {
+    fn from(r: na::Rotation3<f32>) -> Self {
+        let m = r.matrix();
+        Self {
+            forward: Vector3::new(m[(0, 0)], m[(1, 0)], m[(2, 0)]),
+            right: Vector3::new(m[(0, 1)], m[(1, 1)], m[(2, 1)]),
+            up: Vector3::new(m[(0, 2)], m[(1, 2)], m[(2, 2)]),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        game::{Quaternion, Rotator, Vector3},
+        math::RotationMatrix,
+    };
+
+    #[test]
+    fn nalgebra_agrees_on_rotations() {
+        let rotator = Rotator::new(0.4, -1.1, 2.2);
+        let v = Vector3::new(1.0, -2.0, 0.5);
+        let expected = RotationMatrix::from(rotator) * v;
+
+        let q: na::UnitQuaternion<f32> = Quaternion::from(rotator).into();
+        let actual = Vector3::from(q * na::Vector3::from(v));
+        assert!((actual - expected).length() < 1e-5);
+
+        let r: na::Rotation3<f32> = RotationMatrix::from(rotator).into();
+        let actual = Vector3::from(r * na::Vector3::from(v));
+        assert!((actual - expected).length() < 1e-5);
+
+        let round_trip = Quaternion::from(na::UnitQuaternion::from(Quaternion::from(rotator)));
+        assert_eq!(round_trip, Quaternion::from(rotator));
+    }
+}
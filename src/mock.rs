@@ -0,0 +1,289 @@
+//! An in-process stand-in for the RLBot DLL.
+
+use crate::{backend::CoreBackend, ffi::RLBotCoreStatus};
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// A scriptable, in-memory [`CoreBackend`].
+///
+/// Incoming data (packets, physics ticks, etc.) is queued up with the `push_*`
+/// methods. Each call to the matching getter consumes one buffer from the
+/// queue. Once the queue is empty, the last buffer sticks around and is
+/// returned again on every later call, just like the real game keeps returning
+/// its latest packet until a new one arrives. Before anything is pushed, the
+/// getters return `None`.
+///
+/// Outgoing calls (player input, rendering, etc.) are recorded and can be
+/// inspected afterwards.
+///
+/// `MockBackend` is a cheap handle to shared state, so keep a clone around
+/// after handing one to [`init_with_backend`](crate::init_with_backend).
+///
+/// # Example
+///
+/// ```
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let backend = rlbot::MockBackend::new();
+/// let rlbot = rlbot::init_with_backend(backend.clone())?;
+///
+/// rlbot.update_player_input(0, &Default::default())?;
+/// assert_eq!(backend.player_inputs().len(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct MockBackend {
+    state: Arc<Mutex<MockState>>,
+}
+
+#[derive(Default)]
+struct MockState {
+    field_infos: StickyQueue<Vec<u8>>,
+    live_data_packets: StickyQueue<Vec<u8>>,
+    rigid_body_ticks: StickyQueue<Vec<u8>>,
+    ball_predictions: StickyQueue<Vec<u8>>,
+    quick_chat_messages: StickyQueue<Vec<u8>>,
+    game_states: Vec<Vec<u8>>,
+    match_settings: Vec<Vec<u8>>,
+    quick_chats: Vec<Vec<u8>>,
+    player_inputs: Vec<Vec<u8>>,
+    render_groups: Vec<Vec<u8>>,
+    status: Option<RLBotCoreStatus>,
+}
+
+impl MockBackend {
+    /// Constructs a new `MockBackend` with nothing queued.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Queues a [`flat::FieldInfo`](crate::flat::FieldInfo) buffer.
+    pub fn push_field_info(&self, buffer: impl Into<Vec<u8>>) {
+        self.state().field_infos.push(buffer.into());
+    }
+
+    /// Queues a [`flat::GameTickPacket`](crate::flat::GameTickPacket) buffer.
+    pub fn push_live_data_packet(&self, buffer: impl Into<Vec<u8>>) {
+        self.state().live_data_packets.push(buffer.into());
+    }
+
+    /// Queues a [`flat::RigidBodyTick`](crate::flat::RigidBodyTick) buffer.
+    pub fn push_rigid_body_tick(&self, buffer: impl Into<Vec<u8>>) {
+        self.state().rigid_body_ticks.push(buffer.into());
+    }
+
+    /// Queues a [`flat::BallPrediction`](crate::flat::BallPrediction) buffer.
+    pub fn push_ball_prediction(&self, buffer: impl Into<Vec<u8>>) {
+        self.state().ball_predictions.push(buffer.into());
+    }
+
+    /// Queues a [`flat::QuickChatMessages`](crate::flat::QuickChatMessages)
+    /// buffer. It is returned as-is, whatever player and message index are
+    /// asked for.
+    pub fn push_quick_chat_messages(&self, buffer: impl Into<Vec<u8>>) {
+        self.state().quick_chat_messages.push(buffer.into());
+    }
+
+    /// Makes every subsequent outgoing call fail with the given status, or
+    /// succeed again if `status` is [`Success`](RLBotCoreStatus::Success).
+    /// Failed calls are not recorded.
+    pub fn set_status(&self, status: RLBotCoreStatus) {
+        self.state().status = match status {
+            RLBotCoreStatus::Success => None,
+            status => Some(status),
+        };
+    }
+
+    /// Returns every [`flat::DesiredGameState`](crate::flat::DesiredGameState)
+    /// buffer that was sent, oldest first.
+    pub fn game_states(&self) -> Vec<Vec<u8>> {
+        self.state().game_states.clone()
+    }
+
+    /// Returns every [`flat::MatchSettings`](crate::flat::MatchSettings)
+    /// buffer that was sent, oldest first.
+    pub fn match_settings(&self) -> Vec<Vec<u8>> {
+        self.state().match_settings.clone()
+    }
+
+    /// Returns every [`flat::QuickChat`](crate::flat::QuickChat) buffer that
+    /// was sent, oldest first.
+    pub fn quick_chats(&self) -> Vec<Vec<u8>> {
+        self.state().quick_chats.clone()
+    }
+
+    /// Returns every [`flat::PlayerInput`](crate::flat::PlayerInput) buffer
+    /// that was sent, oldest first.
+    pub fn player_inputs(&self) -> Vec<Vec<u8>> {
+        self.state().player_inputs.clone()
+    }
+
+    /// Returns every [`flat::RenderGroup`](crate::flat::RenderGroup) buffer
+    /// that was sent, oldest first.
+    pub fn render_groups(&self) -> Vec<Vec<u8>> {
+        self.state().render_groups.clone()
+    }
+
+    fn record(
+        &self,
+        f: impl FnOnce(&mut MockState) -> &mut Vec<Vec<u8>>,
+        buffer: &[u8],
+    ) -> RLBotCoreStatus {
+        let mut state = self.state();
+        if let Some(status) = state.status {
+            return status;
+        }
+        f(&mut state).push(buffer.to_vec());
+        RLBotCoreStatus::Success
+    }
+
+    fn take(&self, f: impl FnOnce(&mut MockState) -> &mut StickyQueue<Vec<u8>>) -> Option<Vec<u8>> {
+        f(&mut self.state()).next()
+    }
+}
+
+/// A queue whose most recently consumed item sticks around after the queue
+/// runs dry.
+pub(crate) struct StickyQueue<T> {
+    pending: VecDeque<T>,
+    current: Option<T>,
+}
+
+impl<T> Default for StickyQueue<T> {
+    fn default() -> Self {
+        Self {
+            pending: VecDeque::new(),
+            current: None,
+        }
+    }
+}
+
+impl<T: Clone> StickyQueue<T> {
+    pub fn push(&mut self, item: T) {
+        self.pending.push_back(item);
+    }
+
+    /// Consumes the next item, if there is one, and returns the current item.
+    pub fn next(&mut self) -> Option<T> {
+        if let Some(item) = self.pending.pop_front() {
+            self.current = Some(item);
+        }
+        self.current.clone()
+    }
+
+    /// Consumes items for as long as they satisfy the predicate, and returns
+    /// the current item.
+    pub fn next_while(&mut self, mut f: impl FnMut(&T) -> bool) -> Option<T> {
+        while let Some(item) = self.pending.front() {
+            if !f(item) {
+                break;
+            }
+            self.current = self.pending.pop_front();
+        }
+        self.current.clone()
+    }
+}
+
+impl CoreBackend for MockBackend {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.take(|s| &mut s.field_infos)
+    }
+
+    fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.take(|s| &mut s.live_data_packets)
+    }
+
+    fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.take(|s| &mut s.rigid_body_ticks)
+    }
+
+    fn get_ball_prediction(&self) -> Option<Vec<u8>> {
+        self.take(|s| &mut s.ball_predictions)
+    }
+
+    fn set_game_state(&self, desired_game_state_buffer: &[u8]) -> RLBotCoreStatus {
+        self.record(|s| &mut s.game_states, desired_game_state_buffer)
+    }
+
+    fn start_match_flatbuffer(&self, match_settings_buffer: &[u8]) -> RLBotCoreStatus {
+        self.record(|s| &mut s.match_settings, match_settings_buffer)
+    }
+
+    fn send_quick_chat(&self, quick_chat_buffer: &[u8]) -> RLBotCoreStatus {
+        self.record(|s| &mut s.quick_chats, quick_chat_buffer)
+    }
+
+    fn receive_chat(
+        &self,
+        _player_index: i32,
+        _team: i32,
+        _last_message_index: i32,
+    ) -> Option<Vec<u8>> {
+        self.take(|s| &mut s.quick_chat_messages)
+    }
+
+    fn update_player_input_flatbuffer(&self, player_input_buffer: &[u8]) -> RLBotCoreStatus {
+        self.record(|s| &mut s.player_inputs, player_input_buffer)
+    }
+
+    fn render_group(&self, render_group_buffer: &[u8]) -> RLBotCoreStatus {
+        self.record(|s| &mut s.render_groups, render_group_buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{backend::CoreBackend, ffi::RLBotCoreStatus, mock::MockBackend};
+
+    #[test]
+    fn last_buffer_sticks() {
+        let backend = MockBackend::new();
+        assert_eq!(backend.update_live_data_packet_flatbuffer(), None);
+
+        backend.push_live_data_packet(vec![1]);
+        backend.push_live_data_packet(vec![2]);
+        assert_eq!(backend.update_live_data_packet_flatbuffer(), Some(vec![1]));
+        assert_eq!(backend.update_live_data_packet_flatbuffer(), Some(vec![2]));
+        assert_eq!(backend.update_live_data_packet_flatbuffer(), Some(vec![2]));
+
+        backend.push_live_data_packet(vec![3]);
+        assert_eq!(backend.update_live_data_packet_flatbuffer(), Some(vec![3]));
+    }
+
+    #[test]
+    fn packet_into_reuses_the_buffer() {
+        let backend = MockBackend::new();
+        let mut buf = Vec::with_capacity(64);
+        buf.push(9);
+        assert!(!backend.update_live_data_packet_flatbuffer_into(&mut buf));
+        assert_eq!(buf, vec![9]);
+
+        backend.push_live_data_packet(vec![1, 2]);
+        assert!(backend.update_live_data_packet_flatbuffer_into(&mut buf));
+        assert_eq!(buf, vec![1, 2]);
+        assert!(buf.capacity() >= 64);
+    }
+
+    #[test]
+    fn failed_calls_are_not_recorded() {
+        let backend = MockBackend::new();
+        backend.set_status(RLBotCoreStatus::QuickChatRateExceeded);
+        assert_eq!(
+            backend.send_quick_chat(&[1]),
+            RLBotCoreStatus::QuickChatRateExceeded,
+        );
+        backend.set_status(RLBotCoreStatus::Success);
+        assert_eq!(backend.send_quick_chat(&[2]), RLBotCoreStatus::Success);
+        assert_eq!(backend.quick_chats(), vec![vec![2]]);
+    }
+}
@@ -0,0 +1,255 @@
+//! Orientation and local-frame helpers for cars.
+
+use crate::{
+    game::{Physics, PlayerInfo, PlayerRigidBodyState, RigidBodyState, Vector3},
+    math::RotationMatrix,
+};
+
+impl Physics {
+    /// Returns the object's orientation as a [`RotationMatrix`].
+    pub fn rotation_matrix(&self) -> RotationMatrix {
+        self.rotation.into()
+    }
+}
+
+impl RigidBodyState {
+    /// Returns the object's orientation as a [`RotationMatrix`].
+    pub fn rotation_matrix(&self) -> RotationMatrix {
+        self.rotation.into()
+    }
+}
+
+impl PlayerInfo {
+    /// Returns a unit vector pointing out the car's nose.
+    pub fn forward(&self) -> Vector3 {
+        self.physics.rotation_matrix().forward
+    }
+
+    /// Returns a unit vector pointing out the car's right side.
+    pub fn right(&self) -> Vector3 {
+        self.physics.rotation_matrix().right
+    }
+
+    /// Returns a unit vector pointing out the car's roof.
+    pub fn up(&self) -> Vector3 {
+        self.physics.rotation_matrix().up
+    }
+
+    /// Transforms a point in world coordinates into the car's local frame,
+    /// where `x` is forward, `y` is right and `z` is up.
+    pub fn to_local(&self, point: Vector3) -> Vector3 {
+        self.frame().to_local(point)
+    }
+
+    /// Transforms a point in the car's local frame back into world
+    /// coordinates. This is the inverse of [`to_local`](PlayerInfo::to_local).
+    pub fn to_world(&self, local: Vector3) -> Vector3 {
+        self.frame().to_world(local)
+    }
+
+    /// Returns the angle in radians the car would need to turn to face
+    /// `target`, ignoring height. Positive means the target is to the right,
+    /// which is the same sign as a steer input towards it.
+    pub fn angle_to(&self, target: Vector3) -> f32 {
+        self.frame().angle_to(target)
+    }
+
+    /// Returns the number of seconds it would take to reach `target` in a
+    /// straight line at the car's current speed. This is infinite if the car
+    /// is not moving.
+    pub fn time_to(&self, target: Vector3) -> f32 {
+        self.frame().time_to(target)
+    }
+
+    fn frame(&self) -> Frame {
+        Frame {
+            location: self.physics.location,
+            rotation: self.physics.rotation_matrix(),
+            velocity: self.physics.velocity,
+        }
+    }
+}
+
+impl PlayerRigidBodyState {
+    /// Returns a unit vector pointing out the car's nose.
+    pub fn forward(&self) -> Vector3 {
+        self.state.rotation_matrix().forward
+    }
+
+    /// Returns a unit vector pointing out the car's right side.
+    pub fn right(&self) -> Vector3 {
+        self.state.rotation_matrix().right
+    }
+
+    /// Returns a unit vector pointing out the car's roof.
+    pub fn up(&self) -> Vector3 {
+        self.state.rotation_matrix().up
+    }
+
+    /// Transforms a point in world coordinates into the car's local frame,
+    /// where `x` is forward, `y` is right and `z` is up.
+    pub fn to_local(&self, point: Vector3) -> Vector3 {
+        self.frame().to_local(point)
+    }
+
+    /// Transforms a point in the car's local frame back into world
+    /// coordinates. This is the inverse of
+    /// [`to_local`](PlayerRigidBodyState::to_local).
+    pub fn to_world(&self, local: Vector3) -> Vector3 {
+        self.frame().to_world(local)
+    }
+
+    /// Returns the angle in radians the car would need to turn to face
+    /// `target`, ignoring height. Positive means the target is to the right,
+    /// which is the same sign as a steer input towards it.
+    pub fn angle_to(&self, target: Vector3) -> f32 {
+        self.frame().angle_to(target)
+    }
+
+    /// Returns the number of seconds it would take to reach `target` in a
+    /// straight line at the car's current speed. This is infinite if the car
+    /// is not moving.
+    pub fn time_to(&self, target: Vector3) -> f32 {
+        self.frame().time_to(target)
+    }
+
+    fn frame(&self) -> Frame {
+        Frame {
+            location: self.state.location,
+            rotation: self.state.rotation_matrix(),
+            velocity: self.state.velocity,
+        }
+    }
+}
+
+/// The parts of a car's state that the helpers above need, regardless of how
+/// its rotation was reported.
+struct Frame {
+    location: Vector3,
+    rotation: RotationMatrix,
+    velocity: Vector3,
+}
+
+impl Frame {
+    fn to_local(&self, point: Vector3) -> Vector3 {
+        self.rotation.transpose() * (point - self.location)
+    }
+
+    fn to_world(&self, local: Vector3) -> Vector3 {
+        self.location + self.rotation * local
+    }
+
+    fn angle_to(&self, target: Vector3) -> f32 {
+        let local = self.to_local(target);
+        local.y.atan2(local.x)
+    }
+
+    fn time_to(&self, target: Vector3) -> f32 {
+        let distance = self.location.distance(target);
+        if distance == 0.0 {
+            return 0.0;
+        }
+        distance / self.velocity.length()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::game::{
+        BoxShape, ControllerState, Physics, PlayerRigidBodyState, Quaternion, RigidBodyState,
+        Rotator, Vector3,
+    };
+    use std::f32::consts::PI;
+
+    fn car(location: Vector3, rotator: Rotator, velocity: Vector3) -> PlayerRigidBodyState {
+        PlayerRigidBodyState {
+            state: RigidBodyState {
+                frame: 0,
+                location,
+                rotation: Quaternion::from(rotator),
+                velocity,
+                angular_velocity: Vector3::ZERO,
+                _non_exhaustive: (),
+            },
+            input: ControllerState::default(),
+            _non_exhaustive: (),
+        }
+    }
+
+    fn assert_near(a: Vector3, b: Vector3) {
+        assert!((a - b).length() < 1e-3, "{:?} != {:?}", a, b);
+    }
+
+    #[test]
+    fn local_frame_round_trips() {
+        let car = car(
+            Vector3::new(100.0, -200.0, 17.0),
+            Rotator::new(0.2, 1.3, -0.4),
+            Vector3::ZERO,
+        );
+        let point = Vector3::new(-500.0, 300.0, 90.0);
+        assert_near(car.to_world(car.to_local(point)), point);
+        let ahead = car.state.location + car.forward() * 10.0;
+        assert_near(car.to_local(ahead), Vector3::new(10.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn angle_to_is_positive_to_the_right() {
+        // Facing +y, the car's right is -x.
+        let car = car(
+            Vector3::ZERO,
+            Rotator::new(0.0, PI / 2.0, 0.0),
+            Vector3::ZERO,
+        );
+        assert!((car.angle_to(Vector3::new(-100.0, 100.0, 0.0)) - PI / 4.0).abs() < 1e-5);
+        assert!((car.angle_to(Vector3::new(100.0, 100.0, 0.0)) + PI / 4.0).abs() < 1e-5);
+        assert!(car.right().x < -0.99);
+    }
+
+    #[test]
+    fn time_to_uses_current_speed() {
+        let moving = car(
+            Vector3::ZERO,
+            Rotator::default(),
+            Vector3::new(500.0, 0.0, 0.0),
+        );
+        assert_eq!(moving.time_to(Vector3::new(1000.0, 0.0, 0.0)), 2.0);
+
+        let parked = car(Vector3::ZERO, Rotator::default(), Vector3::ZERO);
+        assert!(parked.time_to(Vector3::new(1.0, 0.0, 0.0)).is_infinite());
+    }
+
+    #[test]
+    fn box_contains_local_points() {
+        let hitbox = BoxShape {
+            length: 118.0,
+            width: 84.2,
+            height: 36.2,
+            _non_exhaustive: (),
+        };
+        let car = car(
+            Vector3::new(0.0, 0.0, 17.0),
+            Rotator::new(0.0, PI / 2.0, 0.0),
+            Vector3::ZERO,
+        );
+        assert!(hitbox.contains(car.to_local(Vector3::new(0.0, 50.0, 17.0))));
+        assert!(!hitbox.contains(car.to_local(Vector3::new(50.0, 0.0, 17.0))));
+    }
+
+    #[test]
+    fn physics_and_rigid_body_agree() {
+        let rotator = Rotator::new(-0.3, 2.5, 0.9);
+        let physics = Physics {
+            location: Vector3::ZERO,
+            rotation: rotator,
+            velocity: Vector3::ZERO,
+            angular_velocity: Vector3::ZERO,
+            _non_exhaustive: (),
+        };
+        let car = car(Vector3::ZERO, rotator, Vector3::ZERO);
+        let (a, b) = (physics.rotation_matrix(), car.state.rotation_matrix());
+        assert_near(a.forward, b.forward);
+        assert_near(a.right, b.right);
+        assert_near(a.up, b.up);
+    }
+}
@@ -1,8 +1,16 @@
 #![allow(clippy::float_cmp)]
 
-use crate::{ffi, ffi::LiveDataPacket, game::GameTickPacket, rlbot::RLBot};
+use crate::{
+    error::{Error, TimeoutError},
+    ffi,
+    ffi::LiveDataPacket,
+    game::GameTickPacket,
+    game_view::GameTickPacketView,
+    rlbot::RLBot,
+    verify,
+};
 use std::{
-    error::Error,
+    thread::{self, sleep},
     time::{Duration, Instant},
 };
 
@@ -10,35 +18,137 @@ use std::{
 pub struct Packeteer<'a> {
     rlbot: &'a RLBot,
     ratelimiter: ratelimit::Limiter,
+    options: PacketeerOptions,
     prev_game_time: f32,
+    /// When the previous packet arrived, and how long we expect to wait for
+    /// the one after it. Only tracked in adaptive mode.
+    expected_next: Option<(Instant, Duration)>,
+    /// When the most recent fetch started. Once a packet is returned, this is
+    /// when the fetch that found it started.
+    fetched: Instant,
+    /// Holds the raw packet behind the most recent
+    /// [`GameTickPacketView`], so its allocation can be reused.
+    buffer: Vec<u8>,
+}
+
+/// Options for customizing how a [`Packeteer`] waits for packets.
+///
+/// Pass this to [`RLBot::packeteer_with`](crate::RLBot::packeteer_with).
+///
+/// # Example
+///
+/// ```no_run
+/// # use std::time::Duration;
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let rlbot = rlbot::init()?;
+/// let options = rlbot::PacketeerOptions::new()
+///     .adaptive(true)
+///     .timeout(Duration::from_secs(1));
+/// let mut packets = rlbot.packeteer_with(options);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct PacketeerOptions {
+    poll_interval: Duration,
+    adaptive: bool,
+    timeout: Duration,
+    busy_wait: bool,
+}
+
+impl Default for PacketeerOptions {
+    fn default() -> Self {
+        Self {
+            // The goal is never to miss any packets. But if we poll too often, the
+            // game crashes, so it's a fine line. With an interval of 3ms we can
+            // catch 333 updates per second. That should be plenty.
+            poll_interval: Duration::from_millis(3),
+            adaptive: false,
+            timeout: Duration::from_secs(10),
+            busy_wait: false,
+        }
+    }
+}
+
+impl PacketeerOptions {
+    /// Constructs a new `PacketeerOptions` with the default settings: poll
+    /// every 3ms, and give up after ten seconds.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets how long to wait between each check for a new packet.
+    pub fn poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Enables or disables adaptive polling.
+    ///
+    /// In adaptive mode, the gap in `seconds_elapsed` between the last two
+    /// packets is used to guess when the next packet will arrive. After each
+    /// packet, the `Packeteer` sleeps until shortly before that time, and only
+    /// then starts polling at the usual interval. This keeps the number of
+    /// wasted calls into RLBot to a minimum.
+    pub fn adaptive(mut self, adaptive: bool) -> Self {
+        self.adaptive = adaptive;
+        self
+    }
+
+    /// Sets how long to wait for a new packet before returning
+    /// [`Error::Timeout`].
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Polls again straight away, rather than waiting out the poll interval.
+    /// This is for lockstep mode, where the game waits for the bot, so every
+    /// moment spent sleeping is a moment the game is stalled.
+    pub(crate) fn busy_wait(mut self, busy_wait: bool) -> Self {
+        self.busy_wait = busy_wait;
+        self
+    }
 }
 
 impl<'a> Packeteer<'a> {
-    pub(crate) fn new(rlbot: &'a RLBot) -> Self {
-        // The goal is never to miss any packets. But if we poll too often, the
-        // game crashes, so it's a fine line. With an interval of 3ms we can
-        // catch 333 updates per second. That should be plenty.
+    pub(crate) fn new(rlbot: &'a RLBot, options: PacketeerOptions) -> Self {
         let ratelimiter = ratelimit::Builder::new()
-            .interval(Duration::from_millis(3))
+            .interval(options.poll_interval)
             .build();
 
         Self {
             rlbot,
             ratelimiter,
+            options,
             prev_game_time: 0.0,
+            expected_next: None,
+            fetched: Instant::now(),
+            buffer: Vec::new(),
         }
     }
 
+    /// Returns when the fetch that found the most recent packet started.
+    pub(crate) fn fetched(&self) -> Instant {
+        self.fetched
+    }
+
+    #[cfg(feature = "async")]
+    pub(crate) fn poll_interval(&self) -> Duration {
+        self.options.poll_interval
+    }
+
     /// Blocks until we receive the next unique [`GameTickPacket`], and then
     /// returns it.
     ///
     /// # Errors
     ///
-    /// This function returns an error if ten seconds pass without a new
+    /// This function returns [`Error::Timeout`] if ten seconds (or the
+    /// [configured timeout](PacketeerOptions::timeout)) pass without a new
     /// packet being received. The assumption is that the game froze or
     /// crashed, and waiting longer will not help.
     #[allow(clippy::should_implement_trait)]
-    pub fn next(&mut self) -> Result<GameTickPacket, Box<dyn Error>> {
+    pub fn next(&mut self) -> Result<GameTickPacket, Error> {
         self.next_flatbuffer().map(From::from)
     }
 
@@ -46,8 +156,13 @@ impl<'a> Packeteer<'a> {
     ///
     /// If there is a packet that is newer than the previous packet, it is
     /// returned. Otherwise, `None` is returned.
-    pub fn try_next(&mut self) -> Option<GameTickPacket> {
-        self.try_next_flat().map(From::from)
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::InvalidFlatbuffer`] if RLBot sends a
+    /// packet that cannot be decoded.
+    pub fn try_next(&mut self) -> Result<Option<GameTickPacket>, Error> {
+        self.try_next_flat()
     }
 
     /// Blocks until we receive the next unique [`ffi::LiveDataPacket`], and
@@ -55,14 +170,15 @@ impl<'a> Packeteer<'a> {
     ///
     /// # Errors
     ///
-    /// This function returns an error if ten seconds pass without a new
+    /// This function returns [`Error::Timeout`] if ten seconds (or the
+    /// [configured timeout](PacketeerOptions::timeout)) pass without a new
     /// packet being received. The assumption is that the game froze or
     /// crashed, and waiting longer will not help.
     #[deprecated(
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     #[allow(deprecated)]
-    pub fn next_ffi(&mut self) -> Result<ffi::LiveDataPacket, Box<dyn Error>> {
+    pub fn next_ffi(&mut self) -> Result<ffi::LiveDataPacket, Error> {
         self.spin(Self::try_next_ffi)
     }
 
@@ -74,8 +190,9 @@ impl<'a> Packeteer<'a> {
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     #[allow(deprecated)]
-    pub fn try_next_ffi(&mut self) -> Result<Option<ffi::LiveDataPacket>, Box<dyn Error>> {
+    pub fn try_next_ffi(&mut self) -> Result<Option<ffi::LiveDataPacket>, Error> {
         let mut packet = LiveDataPacket::default();
+        self.fetched = Instant::now();
         self.rlbot
             .interface()
             .update_live_data_packet(&mut packet)?;
@@ -94,45 +211,151 @@ impl<'a> Packeteer<'a> {
     ///
     /// # Errors
     ///
-    /// This function returns an error if ten seconds pass without a new
+    /// This function returns [`Error::Timeout`] if ten seconds (or the
+    /// [configured timeout](PacketeerOptions::timeout)) pass without a new
     /// packet being received. The assumption is that the game froze or
     /// crashed, and waiting longer will not help.
-    pub fn next_flatbuffer(&mut self) -> Result<GameTickPacket, Box<dyn Error>> {
-        self.spin(|this| Ok(this.try_next_flat()))
+    pub fn next_flatbuffer(&mut self) -> Result<GameTickPacket, Error> {
+        self.spin(Self::try_next_flat)
     }
 
     /// Polls for the next unique [`flat::GameTickPacket`].
     ///
     /// If there is a packet that is newer than the previous packet, it is
     /// returned. Otherwise, `None` is returned.
-    pub fn try_next_flat(&mut self) -> Option<GameTickPacket> {
-        if let Some(packet) = self.rlbot.interface().update_live_data_packet_flatbuffer() {
-            let game_time = packet.game_info.seconds_elapsed;
-            if game_time != self.prev_game_time {
-                self.prev_game_time = game_time;
-                return Some(packet);
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::InvalidFlatbuffer`] if RLBot sends a
+    /// packet that cannot be decoded.
+    pub fn try_next_flat(&mut self) -> Result<Option<GameTickPacket>, Error> {
+        self.fetched = Instant::now();
+        if let Some(packet) = self
+            .rlbot
+            .interface()
+            .update_live_data_packet_flatbuffer()?
+        {
+            if self.observe(
+                packet.game_info.seconds_elapsed,
+                packet.game_info.game_speed,
+            ) {
+                return Ok(Some(packet));
             }
         }
-        None
+        Ok(None)
+    }
+
+    /// Blocks until we receive the next unique packet, and then returns a
+    /// borrowed view of it.
+    ///
+    /// Unlike [`next`](Packeteer::next), this does not decode the packet up
+    /// front, and reuses the same buffer for every packet, so it does not
+    /// allocate once warmed up. The view borrows the `Packeteer`, so it must
+    /// be dropped before asking for the next one.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::Timeout`] if ten seconds (or the
+    /// [configured timeout](PacketeerOptions::timeout)) pass without a new
+    /// packet being received. The assumption is that the game froze or
+    /// crashed, and waiting longer will not help.
+    pub fn next_view(&mut self) -> Result<GameTickPacketView<'_>, Error> {
+        self.spin(|this| Ok(if this.poll_buffer()? { Some(()) } else { None }))?;
+        Ok(GameTickPacketView::from_verified(&self.buffer))
+    }
+
+    /// Polls for the next unique packet, and returns a borrowed view of it.
+    ///
+    /// If there is a packet that is newer than the previous packet, it is
+    /// returned. Otherwise, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::InvalidFlatbuffer`] if RLBot sends a
+    /// packet that cannot be decoded.
+    pub fn try_next_view(&mut self) -> Result<Option<GameTickPacketView<'_>>, Error> {
+        if self.poll_buffer()? {
+            Ok(Some(GameTickPacketView::from_verified(&self.buffer)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Fetches the current packet into `self.buffer`, and returns true if it
+    /// is newer than the previous packet.
+    fn poll_buffer(&mut self) -> Result<bool, Error> {
+        let interface = self.rlbot.interface();
+        self.fetched = Instant::now();
+        if !interface.update_live_data_packet_flatbuffer_into(&mut self.buffer) {
+            return Ok(false);
+        }
+
+        verify::game_tick_packet(&self.buffer)?;
+        let game_info = GameTickPacketView::from_verified(&self.buffer).game_info();
+        Ok(self.observe(game_info.seconds_elapsed, game_info.game_speed))
+    }
+
+    /// Records that a packet was received, and returns true if it is newer
+    /// than the previous packet.
+    fn observe(&mut self, game_time: f32, game_speed: f32) -> bool {
+        if game_time == self.prev_game_time {
+            return false;
+        }
+
+        if self.options.adaptive {
+            self.expect_next(game_time - self.prev_game_time, game_speed);
+        }
+        self.prev_game_time = game_time;
+        true
+    }
+
+    /// Guess when the next packet will arrive, given the gap in game time
+    /// since the previous packet.
+    fn expect_next(&mut self, game_time_delta: f32, game_speed: f32) {
+        // Pauses, goal replays, etc. make for huge gaps that say nothing about
+        // the tick rate, so ignore anything that doesn't look like a tick.
+        let game_speed = if game_speed > 0.0 { game_speed } else { 1.0 };
+        let delta = game_time_delta / game_speed;
+        self.expected_next = if self.prev_game_time != 0.0 && delta > 0.0 && delta < 0.1 {
+            Some((Instant::now(), Duration::from_secs_f32(delta)))
+        } else {
+            None
+        };
     }
 
     /// Keep trying `f` until the timeout elapses.
-    fn spin<R>(
-        &mut self,
-        f: impl Fn(&mut Self) -> Result<Option<R>, Box<dyn Error>>,
-    ) -> Result<R, Box<dyn Error>> {
+    fn spin<R>(&mut self, f: impl Fn(&mut Self) -> Result<Option<R>, Error>) -> Result<R, Error> {
         let start = Instant::now();
 
+        if let Some((received, delta)) = self.expected_next {
+            // Wake up a little early, so the packet is not missed by much if it
+            // comes sooner than expected.
+            let wake = received
+                + delta
+                    .checked_sub(self.options.poll_interval)
+                    .unwrap_or_default();
+            let now = Instant::now();
+            if wake > now {
+                sleep(wake - now);
+            }
+        }
+
         loop {
-            self.ratelimiter.wait();
+            if self.options.busy_wait {
+                thread::yield_now();
+            } else {
+                self.ratelimiter.wait();
+            }
 
             if let Some(tick) = f(self)? {
                 return Ok(tick);
             }
 
             let elapsed = Instant::now() - start;
-            if elapsed > Duration::from_secs(10) {
-                return Err(From::from("no physics tick received after ten seconds"));
+            if elapsed > self.options.timeout {
+                return Err(Error::Timeout(TimeoutError {
+                    timeout: self.options.timeout,
+                }));
             }
         }
     }
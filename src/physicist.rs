@@ -1,6 +1,9 @@
-use crate::{ffi, game, rlbot::RLBot};
+use crate::{
+    error::{Error, TimeoutError},
+    ffi, game,
+    rlbot::RLBot,
+};
 use std::{
-    error::Error,
     mem,
     time::{Duration, Instant},
 };
@@ -15,12 +18,13 @@ pub struct Physicist<'a> {
 
 impl<'a> Physicist<'a> {
     const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+    // Physics ticks happen at 120Hz. The goal is never to miss any. But if we poll
+    // too often, the game crashes, so space out the checks.
+    pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(1);
 
     pub(crate) fn new(rlbot: &'a RLBot) -> Self {
-        // Physics ticks happen at 120Hz. The goal is never to miss any. But if we poll
-        // too often, the game crashes, so space out the checks.
         let ratelimiter = ratelimit::Builder::new()
-            .interval(Duration::from_millis(1))
+            .interval(Self::POLL_INTERVAL)
             .build();
 
         Self {
@@ -42,7 +46,7 @@ impl<'a> Physicist<'a> {
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     #[allow(deprecated)]
-    pub fn next(&mut self) -> Result<ffi::RigidBodyTick, Box<dyn Error>> {
+    pub fn next(&mut self) -> Result<ffi::RigidBodyTick, Error> {
         self.spin(|this| Ok(this.try_next()?), Self::DEFAULT_TIMEOUT)
     }
 
@@ -54,7 +58,7 @@ impl<'a> Physicist<'a> {
         note = "the struct-based methods are deprecated; use the flatbuffer equivalents instead"
     )]
     #[allow(deprecated)]
-    pub fn try_next(&mut self) -> Result<Option<ffi::RigidBodyTick>, Box<dyn Error>> {
+    pub fn try_next(&mut self) -> Result<Option<ffi::RigidBodyTick>, Error> {
         let mut result = unsafe { mem::uninitialized() };
         self.rlbot.interface().update_rigid_body_tick(&mut result)?;
         if result.Ball.State.Frame != self.prev_ball_frame {
@@ -72,8 +76,8 @@ impl<'a> Physicist<'a> {
     /// This function returns an error if ten seconds pass without a new tick
     /// being received. The assumption is that the game froze or crashed, and
     /// waiting longer will not help.
-    pub fn next_flat(&mut self) -> Result<game::RigidBodyTick, Box<dyn Error>> {
-        self.spin(|this| Ok(this.try_next_flat()), Self::DEFAULT_TIMEOUT)
+    pub fn next_flat(&mut self) -> Result<game::RigidBodyTick, Error> {
+        self.spin(Self::try_next_flat, Self::DEFAULT_TIMEOUT)
     }
 
     /// Block until the next physics tick occurs, and then return it.
@@ -83,17 +87,22 @@ impl<'a> Physicist<'a> {
     pub fn next_flat_with_timeout(
         &mut self,
         timeout: Duration,
-    ) -> Result<game::RigidBodyTick, Box<dyn Error>> {
-        self.spin(|this| Ok(this.try_next_flat()), timeout)
+    ) -> Result<game::RigidBodyTick, Error> {
+        self.spin(Self::try_next_flat, timeout)
     }
 
     /// Polls for a new physics tick.
     ///
     /// If there is a tick that is newer than the previous tick, it is
     /// returned. Otherwise, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::InvalidFlatbuffer`] if RLBot sends a
+    /// tick that cannot be decoded.
     #[allow(clippy::redundant_closure)]
-    pub fn try_next_flat(&mut self) -> Option<game::RigidBodyTick> {
-        if let Some(tick) = self.rlbot.interface().update_rigid_body_tick_flatbuffer() {
+    pub fn try_next_flat(&mut self) -> Result<Option<game::RigidBodyTick>, Error> {
+        if let Some(tick) = self.rlbot.interface().update_rigid_body_tick_flatbuffer()? {
             let ball = &tick.ball;
             match ball
                 .as_ref()
@@ -102,20 +111,20 @@ impl<'a> Physicist<'a> {
             {
                 Some(ball_frame) if ball_frame != self.prev_ball_frame => {
                     self.prev_ball_frame = ball_frame;
-                    return Some(tick);
+                    return Ok(Some(tick));
                 }
                 _ => {}
             }
         }
-        None
+        Ok(None)
     }
 
     /// Keep trying `f` until the timeout elapses.
     fn spin<R>(
         &mut self,
-        f: impl Fn(&mut Self) -> Result<Option<R>, Box<dyn Error>>,
+        f: impl Fn(&mut Self) -> Result<Option<R>, Error>,
         timeout: Duration,
-    ) -> Result<R, Box<dyn Error>> {
+    ) -> Result<R, Error> {
         let start = Instant::now();
 
         loop {
@@ -127,7 +136,7 @@ impl<'a> Physicist<'a> {
 
             let elapsed = Instant::now() - start;
             if elapsed > timeout {
-                return Err(From::from("no physics tick received within the timeout"));
+                return Err(Error::Timeout(TimeoutError { timeout }));
             }
         }
     }
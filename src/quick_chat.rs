@@ -0,0 +1,298 @@
+//! Sending and receiving quick chats.
+
+pub use crate::flat::QuickChatSelection;
+use crate::{
+    error::{Error, RateLimitError, TimeoutError},
+    flat,
+    game::QuickChat,
+    rlbot::RLBot,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    mem,
+    time::{Duration, Instant},
+};
+
+/// The highest discriminant of [`QuickChatSelection`]. The discriminants are
+/// contiguous from zero.
+pub(crate) const MAX_SELECTION: i8 = QuickChatSelection::Custom_Compliments_Pro as i8;
+
+/// The framework lets each player send this many chats...
+const MAX_CHAT_COUNT: u32 = 5;
+/// ...within this long of the first one.
+const MAX_CHAT_RATE: Duration = Duration::from_secs(2);
+
+impl QuickChatSelection {
+    /// Returns the selection with the given discriminant, or `None` if it is
+    /// out of range.
+    pub fn from_index(index: i8) -> Option<Self> {
+        if !(0..=MAX_SELECTION).contains(&index) {
+            return None;
+        }
+        // This is sound because the enum is `#[repr(i8)]`, its discriminants
+        // are contiguous, and we just checked the bounds.
+        Some(unsafe { mem::transmute::<i8, Self>(index) })
+    }
+
+    /// Iterates over every quick chat selection, in order.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rlbot::QuickChatSelection;
+    ///
+    /// let all: Vec<_> = QuickChatSelection::all().collect();
+    /// assert_eq!(all[0], QuickChatSelection::Information_IGotIt);
+    /// ```
+    pub fn all() -> impl Iterator<Item = Self> {
+        (0..=MAX_SELECTION).filter_map(Self::from_index)
+    }
+}
+
+/// Builds a [`flat::QuickChat`] flatbuffer.
+pub(crate) fn build_quick_chat(
+    player_index: i32,
+    selection: QuickChatSelection,
+    team_only: bool,
+) -> flatbuffers::FlatBufferBuilder<'static> {
+    let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(64);
+    let root = flat::QuickChat::create(
+        &mut builder,
+        &flat::QuickChatArgs {
+            quickChatSelection: selection,
+            playerIndex: player_index,
+            teamOnly: team_only,
+            ..Default::default()
+        },
+    );
+    builder.finish(root, None);
+    builder
+}
+
+/// Drops chats the same way the framework does, so bots behave the same
+/// whether or not they are launched by it.
+///
+/// Each player gets a window which starts at their first chat. Within the
+/// window, at most [`MAX_CHAT_COUNT`] chats are allowed. Once the window has
+/// lasted [`MAX_CHAT_RATE`], it resets.
+#[derive(Default)]
+pub(crate) struct QuickChatLimiter {
+    windows: HashMap<i32, ChatWindow>,
+}
+
+struct ChatWindow {
+    start: Instant,
+    count: u32,
+}
+
+impl QuickChatLimiter {
+    /// Returns an error if a chat sent now should be dropped. Chats only count
+    /// against the limit once they are [recorded](QuickChatLimiter::record).
+    pub fn check(&mut self, player_index: i32, now: Instant) -> Result<(), RateLimitError> {
+        let window = self.windows.entry(player_index).or_insert(ChatWindow {
+            start: now,
+            count: 0,
+        });
+        let elapsed = now.duration_since(window.start);
+        if elapsed >= MAX_CHAT_RATE {
+            window.start = now;
+            window.count = 0;
+        }
+
+        if window.count >= MAX_CHAT_COUNT {
+            return Err(RateLimitError {
+                retry_after: MAX_CHAT_RATE - elapsed,
+            });
+        }
+        Ok(())
+    }
+
+    /// Counts a chat which passed [`check`](QuickChatLimiter::check) and was
+    /// sent successfully.
+    pub fn record(&mut self, player_index: i32, now: Instant) {
+        let window = self.windows.entry(player_index).or_insert(ChatWindow {
+            start: now,
+            count: 0,
+        });
+        window.count += 1;
+    }
+}
+
+/// An iterator-like object that yields quick chats as they are sent.
+///
+/// Chats are yielded in the order they were sent, and each one is yielded only
+/// once. Versions of RLBot that cannot receive chats behave as if nobody ever
+/// chats.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// let rlbot = rlbot::init()?;
+/// let mut inbox = rlbot.quick_chat_inbox(0, 0).ignore_own(true);
+/// while let Some(chat) = inbox.try_next()? {
+///     println!("player {} said {:?}", chat.player_index, chat.selection);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct QuickChatInbox<'a> {
+    rlbot: &'a RLBot,
+    ratelimiter: ratelimit::Limiter,
+    player_index: i32,
+    team: i32,
+    ignore_own: bool,
+    last_message_index: i32,
+    pending: VecDeque<QuickChat>,
+}
+
+impl<'a> QuickChatInbox<'a> {
+    const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+    // Chats are rare, and nobody will notice if one is answered a few
+    // milliseconds late, so there's no need to poll as often as for packets.
+    const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+    pub(crate) fn new(rlbot: &'a RLBot, player_index: i32, team: i32) -> Self {
+        let ratelimiter = ratelimit::Builder::new()
+            .interval(Self::POLL_INTERVAL)
+            .build();
+
+        Self {
+            rlbot,
+            ratelimiter,
+            player_index,
+            team,
+            ignore_own: false,
+            last_message_index: -1,
+            pending: VecDeque::new(),
+        }
+    }
+
+    /// Sets whether to skip chats sent by the player this inbox belongs to.
+    pub fn ignore_own(mut self, ignore_own: bool) -> Self {
+        self.ignore_own = ignore_own;
+        self
+    }
+
+    /// Blocks until the next unseen [`QuickChat`] is sent, and then returns
+    /// it.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::Timeout`] if ten seconds pass without a
+    /// new chat. Unlike packets, chats can stop for any length of time without
+    /// anything being wrong, so use
+    /// [`next_with_timeout`](QuickChatInbox::next_with_timeout) to wait longer.
+    #[allow(clippy::should_implement_trait)]
+    pub fn next(&mut self) -> Result<QuickChat, Error> {
+        self.next_with_timeout(Self::DEFAULT_TIMEOUT)
+    }
+
+    /// Blocks until the next unseen [`QuickChat`] is sent, and then returns
+    /// it.
+    ///
+    /// This works the same as `next`, but lets the caller choose the timeout.
+    pub fn next_with_timeout(&mut self, timeout: Duration) -> Result<QuickChat, Error> {
+        let start = Instant::now();
+
+        loop {
+            self.ratelimiter.wait();
+
+            if let Some(chat) = self.try_next()? {
+                return Ok(chat);
+            }
+
+            let elapsed = Instant::now() - start;
+            if elapsed > timeout {
+                return Err(Error::Timeout(TimeoutError { timeout }));
+            }
+        }
+    }
+
+    /// Polls for the next unseen [`QuickChat`].
+    ///
+    /// If a chat was sent since the previous one that was returned, it is
+    /// returned. Otherwise, `None` is returned.
+    ///
+    /// # Errors
+    ///
+    /// This function returns [`Error::InvalidFlatbuffer`] if RLBot sends
+    /// messages that cannot be decoded.
+    pub fn try_next(&mut self) -> Result<Option<QuickChat>, Error> {
+        if self.pending.is_empty() {
+            self.poll()?;
+        }
+        Ok(self.pending.pop_front())
+    }
+
+    /// Fetches any new chats into `self.pending`.
+    fn poll(&mut self) -> Result<(), Error> {
+        let chats = self.rlbot.interface().receive_chat(
+            self.player_index,
+            self.team,
+            self.last_message_index,
+        )?;
+
+        // RLBot should only send newer messages, but be defensive so nothing
+        // is ever yielded twice.
+        let mut chats: Vec<_> = chats
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|chat| chat.message_index > self.last_message_index)
+            .collect();
+        chats.sort_by_key(|chat| chat.message_index);
+        chats.dedup_by_key(|chat| chat.message_index);
+
+        if let Some(last) = chats.last() {
+            self.last_message_index = last.message_index;
+        }
+        let (ignore_own, player_index) = (self.ignore_own, self.player_index);
+        self.pending.extend(
+            chats
+                .into_iter()
+                .filter(|chat| !(ignore_own && chat.player_index == player_index)),
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::quick_chat::{QuickChatLimiter, QuickChatSelection};
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn selections_are_iterable() {
+        let all: Vec<_> = QuickChatSelection::all().collect();
+        assert_eq!(all.len(), 58);
+        for (i, &selection) in all.iter().enumerate() {
+            assert_eq!(selection as usize, i);
+        }
+        assert_eq!(QuickChatSelection::from_index(-1), None);
+        assert_eq!(QuickChatSelection::from_index(58), None);
+    }
+
+    #[test]
+    fn limiter_allows_five_chats_per_window() {
+        let mut limiter = QuickChatLimiter::default();
+        let start = Instant::now();
+        for _ in 0..5 {
+            limiter.check(0, start).unwrap();
+            limiter.record(0, start);
+        }
+
+        let later = start + Duration::from_millis(500);
+        let error = limiter.check(0, later).unwrap_err();
+        assert_eq!(error.retry_after, Duration::from_millis(1500));
+
+        // Other players have their own windows.
+        limiter.check(1, later).unwrap();
+
+        // Chats that were checked but never sent don't count.
+        for _ in 0..10 {
+            limiter.check(2, later).unwrap();
+        }
+
+        limiter.check(0, start + Duration::from_secs(2)).unwrap();
+    }
+}
@@ -1,8 +1,12 @@
 //! Rendering is RLBot's ability to draw directly inside the game window.
 
-use crate::{flat, rlbot::RLBot};
+use crate::{
+    error::{Error, ParseColorError},
+    flat,
+    rlbot::RLBot,
+};
 use flatbuffers::{FlatBufferBuilder, WIPOffset};
-use std::error::Error;
+use std::{collections::HashMap, str::FromStr};
 
 /// A render group in the process of being built.
 ///
@@ -15,14 +19,12 @@ use std::error::Error;
 /// ## Basic rendering
 ///
 /// ```no_run
-/// # use rlbot::flat::ColorArgs;
 /// # use std::error::Error;
 /// #
 /// # fn main() -> Result<(), Box<Error>> {
 /// let rlbot = rlbot::init()?;
 /// let mut group = rlbot.begin_render_group(1234);
-/// let green = group.color_rgb(0, 255, 0);
-/// group.draw_string_2d((10.0, 10.0), (2, 2), "I am text!", green);
+/// group.draw_string_2d((10.0, 10.0), (2, 2), "I am text!", rlbot::Color::GREEN);
 /// group.render()?;
 /// # Ok(())
 /// # }
@@ -41,10 +43,9 @@ use std::error::Error;
 /// # }
 /// ```
 pub struct RenderGroup<'a> {
-    rlbot: &'a RLBot,
-    id: i32,
-    builder: FlatBufferBuilder<'a>,
-    messages: Vec<WIPOffset<flat::RenderMessage<'a>>>,
+    pub(crate) rlbot: &'a RLBot,
+    pub(crate) id: i32,
+    pub(crate) messages: Vec<RenderMessage>,
 }
 
 impl<'a> RenderGroup<'a> {
@@ -52,38 +53,261 @@ impl<'a> RenderGroup<'a> {
         Self {
             rlbot,
             id,
-            builder: FlatBufferBuilder::new_with_capacity(1024),
             messages: Vec::new(),
         }
     }
 }
 
+/// One drawing, kept in our own form until the group is sent, so that the
+/// messages can be split across several groups if need be.
+#[derive(Clone)]
+pub(crate) struct RenderMessage {
+    render_type: flat::RenderType,
+    color: Color,
+    start: flat::Vector3,
+    end: Option<flat::Vector3>,
+    scale: (i32, i32),
+    text: Option<String>,
+    filled: bool,
+}
+
+impl RenderMessage {
+    fn new(render_type: flat::RenderType, color: Color, start: flat::Vector3) -> Self {
+        Self {
+            render_type,
+            color,
+            start,
+            end: None,
+            scale: (1, 1),
+            text: None,
+            filled: false,
+        }
+    }
+
+    /// An upper bound on the bytes this message adds to a render group,
+    /// including its color.
+    pub(crate) fn max_size(&self) -> usize {
+        // The table, its vtable, the offset to it, and a color table, with room
+        // to spare for alignment.
+        const FIXED: usize = 128;
+        // Strings have a length prefix, a nul terminator, and padding.
+        FIXED + self.text.as_ref().map_or(0, |t| t.len() + 8)
+    }
+
+    fn build<'fb>(
+        &self,
+        builder: &mut FlatBufferBuilder<'fb>,
+        colors: &mut HashMap<Color, WIPOffset<flat::Color<'fb>>>,
+    ) -> WIPOffset<flat::RenderMessage<'fb>> {
+        // Each color is written to the buffer once, the first time it's used.
+        let color = *colors.entry(self.color).or_insert_with(|| {
+            let Color { a, r, g, b } = self.color;
+            flat::Color::create(builder, &flat::ColorArgs { a, r, g, b })
+        });
+        let text = self.text.as_ref().map(|t| builder.create_string(t));
+
+        let mut rm = flat::RenderMessageBuilder::new(builder);
+        rm.add_renderType(self.render_type);
+        rm.add_color(color);
+        rm.add_start(&self.start);
+        if let Some(end) = &self.end {
+            rm.add_end(end);
+        }
+        rm.add_scaleX(self.scale.0);
+        rm.add_scaleY(self.scale.1);
+        if let Some(text) = text {
+            rm.add_text(text);
+        }
+        rm.add_isFilled(self.filled);
+        rm.finish()
+    }
+}
+
+/// Serializes a render group containing the given messages.
+pub(crate) fn build_render_group(
+    id: i32,
+    messages: &[RenderMessage],
+) -> FlatBufferBuilder<'static> {
+    let mut builder = FlatBufferBuilder::new_with_capacity(1024);
+    let mut colors = HashMap::new();
+    let messages = messages
+        .iter()
+        .map(|m| m.build(&mut builder, &mut colors))
+        .collect::<Vec<_>>();
+    let messages = builder.create_vector(&messages);
+
+    let render_group = {
+        let mut rg = flat::RenderGroupBuilder::new(&mut builder);
+        rg.add_renderMessages(messages);
+        rg.add_id(id);
+        rg.finish()
+    };
+
+    builder.finish(render_group, None);
+    builder
+}
+
 /// A color that can be used to draw in a [`RenderGroup`].
-#[derive(Copy, Clone)]
-pub struct Color<'a>(WIPOffset<flat::Color<'a>>);
+///
+/// Colors are plain values, so they can be stored in constants or config, and
+/// shared between render groups.
+///
+/// # Examples
+///
+/// ```
+/// use rlbot::Color;
+///
+/// const BOOST: Color = Color::rgb(255, 128, 0);
+/// assert_eq!(BOOST, Color::hex(0xff8000));
+/// assert_eq!("#ff8000".parse(), Ok(BOOST));
+/// assert_eq!(Color::hsv(120.0, 1.0, 1.0), Color::GREEN);
+/// ```
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Color {
+    /// The alpha. 255 is fully opaque, and 0 is fully transparent.
+    pub a: u8,
+    /// The red component.
+    pub r: u8,
+    /// The green component.
+    pub g: u8,
+    /// The blue component.
+    pub b: u8,
+}
 
-impl<'a> RenderGroup<'a> {
-    /// Send the collected drawings to RLBot to be rendered to screen.
-    pub fn render(mut self) -> Result<(), Box<dyn Error>> {
-        let messages = self.builder.create_vector(&self.messages);
-
-        let render_group = {
-            let mut rg = flat::RenderGroupBuilder::new(&mut self.builder);
-            rg.add_renderMessages(messages);
-            rg.add_id(self.id);
-            rg.finish()
+impl Color {
+    /// Fully transparent black.
+    pub const TRANSPARENT: Self = Self::argb(0, 0, 0, 0);
+    /// Black.
+    pub const BLACK: Self = Self::rgb(0, 0, 0);
+    /// White.
+    pub const WHITE: Self = Self::rgb(255, 255, 255);
+    /// Gray.
+    pub const GRAY: Self = Self::rgb(128, 128, 128);
+    /// Red.
+    pub const RED: Self = Self::rgb(255, 0, 0);
+    /// Green.
+    pub const GREEN: Self = Self::rgb(0, 255, 0);
+    /// Blue.
+    pub const BLUE: Self = Self::rgb(0, 0, 255);
+    /// Yellow.
+    pub const YELLOW: Self = Self::rgb(255, 255, 0);
+    /// Cyan.
+    pub const CYAN: Self = Self::rgb(0, 255, 255);
+    /// Magenta.
+    pub const MAGENTA: Self = Self::rgb(255, 0, 255);
+    /// Orange.
+    pub const ORANGE: Self = Self::rgb(255, 128, 0);
+
+    /// Create a color with the given **a**lpha, **r**ed, **g**reen, and
+    /// **b**lue. An alpha of 255 is fully opaque, and 0 is fully transparent.
+    pub const fn argb(a: u8, r: u8, g: u8, b: u8) -> Self {
+        Self { a, r, g, b }
+    }
+
+    /// Create an opaque color with the given **r**ed, **g**reen, and **b**lue.
+    pub const fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self::argb(255, r, g, b)
+    }
+
+    /// Create an opaque color from a hex triplet, e.g. `0xff8000`. The top
+    /// byte is ignored.
+    pub const fn hex(rgb: u32) -> Self {
+        Self::rgb((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8)
+    }
+
+    /// Create an opaque color from a **h**ue in degrees, and a **s**aturation
+    /// and **v**alue from 0.0 to 1.0.
+    pub fn hsv(h: f32, s: f32, v: f32) -> Self {
+        let h = h.rem_euclid(360.0) / 60.0;
+        let s = s.clamp(0.0, 1.0);
+        let v = v.clamp(0.0, 1.0);
+        let c = v * s;
+        let x = c * (1.0 - (h % 2.0 - 1.0).abs());
+        let (r, g, b) = match h as u32 {
+            0 => (c, x, 0.0),
+            1 => (x, c, 0.0),
+            2 => (0.0, c, x),
+            3 => (0.0, x, c),
+            4 => (x, 0.0, c),
+            _ => (c, 0.0, x),
         };
+        let m = v - c;
+        let channel = |n: f32| ((n + m) * 255.0).round() as u8;
+        Self::rgb(channel(r), channel(g), channel(b))
+    }
+
+    /// Returns the same color with a different alpha.
+    pub const fn with_alpha(self, a: u8) -> Self {
+        Self::argb(a, self.r, self.g, self.b)
+    }
 
-        self.builder.finish(render_group, None);
-        let data = self.builder.finished_data();
-        self.rlbot.interface().render_group(data)?;
+    /// Linearly interpolates between two colors, including their alpha. A `t`
+    /// of 0.0 returns `self`, and 1.0 returns `other`.
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let t = t.clamp(0.0, 1.0);
+        let mix = |x: u8, y: u8| (f32::from(x) + (f32::from(y) - f32::from(x)) * t).round() as u8;
+        Self::argb(
+            mix(self.a, other.a),
+            mix(self.r, other.r),
+            mix(self.g, other.g),
+            mix(self.b, other.b),
+        )
+    }
+
+    /// Alpha-blends this color on top of `background`, i.e. the standard
+    /// "source over" operation.
+    pub fn over(self, background: Self) -> Self {
+        let src_a = f32::from(self.a) / 255.0;
+        let dst_a = f32::from(background.a) / 255.0 * (1.0 - src_a);
+        let out_a = src_a + dst_a;
+        if out_a == 0.0 {
+            return Self::TRANSPARENT;
+        }
+        let mix = |src: u8, dst: u8| {
+            ((f32::from(src) * src_a + f32::from(dst) * dst_a) / out_a).round() as u8
+        };
+        Self::argb(
+            (out_a * 255.0).round() as u8,
+            mix(self.r, background.r),
+            mix(self.g, background.g),
+            mix(self.b, background.b),
+        )
+    }
+}
+
+/// Parses `#rrggbb` or `#aarrggbb`. The `#` is optional.
+impl FromStr for Color {
+    type Err = ParseColorError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let digits = s.strip_prefix('#').unwrap_or(s);
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(ParseColorError);
+        }
+        let n = u32::from_str_radix(digits, 16).map_err(|_| ParseColorError)?;
+        match digits.len() {
+            6 => Ok(Self::hex(n)),
+            8 => Ok(Self::hex(n).with_alpha((n >> 24) as u8)),
+            _ => Err(ParseColorError),
+        }
+    }
+}
+
+impl<'a> RenderGroup<'a> {
+    /// Send the collected drawings to RLBot to be rendered to screen.
+    pub fn render(self) -> Result<(), Error> {
+        let builder = build_render_group(self.id, &self.messages);
+        self.rlbot
+            .interface()
+            .render_group(builder.finished_data())?;
         Ok(())
     }
 
     /// Create a color with the given **a**lpha, **r**ed, **g**reen, and
     /// **b**lue. An alpha of 255 is fully opaque, and 0 is fully transparent.
     ///
-    /// A color can only be used with the `RenderGroup` that created it.
+    /// This is the same as [`Color::argb`].
     ///
     /// # Example
     ///
@@ -92,14 +316,13 @@ impl<'a> RenderGroup<'a> {
     /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
     /// let transbluecent = group.color_argb(127, 0, 0, 255);
     /// ```
-    pub fn color_argb(&mut self, a: u8, r: u8, g: u8, b: u8) -> Color<'a> {
-        let args = flat::ColorArgs { a, r, g, b };
-        Color(flat::Color::create(&mut self.builder, &args))
+    pub fn color_argb(&self, a: u8, r: u8, g: u8, b: u8) -> Color {
+        Color::argb(a, r, g, b)
     }
 
     /// Create an opaque color with the given, **r**ed, **g**reen, and **b**lue.
     ///
-    /// A color can only be used with the `RenderGroup` that created it.
+    /// This is the same as [`Color::rgb`].
     ///
     /// # Example
     ///
@@ -108,9 +331,8 @@ impl<'a> RenderGroup<'a> {
     /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
     /// let green = group.color_rgb(0, 255, 0);
     /// ```
-    pub fn color_rgb(&mut self, r: u8, g: u8, b: u8) -> Color<'a> {
-        let args = flat::ColorArgs { a: 255, r, g, b };
-        Color(flat::Color::create(&mut self.builder, &args))
+    pub fn color_rgb(&self, r: u8, g: u8, b: u8) -> Color {
+        Color::rgb(r, g, b)
     }
 
     /// Draw a line using screen coordinates.
@@ -123,21 +345,14 @@ impl<'a> RenderGroup<'a> {
     /// # let green = group.color_rgb(0, 255, 0);
     /// group.draw_line_2d((10.0, 10.0), (100.0, 100.0), green);
     /// ```
-    pub fn draw_line_2d(
-        &mut self,
-        (x1, y1): (f32, f32),
-        (x2, y2): (f32, f32),
-        Color(color): Color<'_>,
-    ) {
-        let start = flat::Vector3::new(x1, y1, 0.0);
-        let end = flat::Vector3::new(x2, y2, 0.0);
-
-        let mut rm = flat::RenderMessageBuilder::new(&mut self.builder);
-        rm.add_renderType(flat::RenderType::DrawLine2D);
-        rm.add_color(color);
-        rm.add_start(&start);
-        rm.add_end(&end);
-        self.messages.push(rm.finish());
+    pub fn draw_line_2d(&mut self, (x1, y1): (f32, f32), (x2, y2): (f32, f32), color: Color) {
+        let mut message = RenderMessage::new(
+            flat::RenderType::DrawLine2D,
+            color,
+            flat::Vector3::new(x1, y1, 0.0),
+        );
+        message.end = Some(flat::Vector3::new(x2, y2, 0.0));
+        self.messages.push(message);
     }
 
     /// Draw a line using world coordinates.
@@ -154,17 +369,15 @@ impl<'a> RenderGroup<'a> {
         &mut self,
         (x1, y1, z1): (f32, f32, f32),
         (x2, y2, z2): (f32, f32, f32),
-        Color(color): Color<'_>,
+        color: Color,
     ) {
-        let start = flat::Vector3::new(x1, y1, z1);
-        let end = flat::Vector3::new(x2, y2, z2);
-
-        let mut rm = flat::RenderMessageBuilder::new(&mut self.builder);
-        rm.add_renderType(flat::RenderType::DrawLine3D);
-        rm.add_color(color);
-        rm.add_start(&start);
-        rm.add_end(&end);
-        self.messages.push(rm.finish());
+        let mut message = RenderMessage::new(
+            flat::RenderType::DrawLine3D,
+            color,
+            flat::Vector3::new(x1, y1, z1),
+        );
+        message.end = Some(flat::Vector3::new(x2, y2, z2));
+        self.messages.push(message);
     }
 
     /// Draw a line with one endpoint in screen coordinates and the other at a
@@ -182,17 +395,15 @@ impl<'a> RenderGroup<'a> {
         &mut self,
         (x1, y1): (f32, f32),
         (x2, y2, z2): (f32, f32, f32),
-        Color(color): Color<'_>,
+        color: Color,
     ) {
-        let start = flat::Vector3::new(x1, y1, 0.0);
-        let end = flat::Vector3::new(x2, y2, z2);
-
-        let mut rm = flat::RenderMessageBuilder::new(&mut self.builder);
-        rm.add_renderType(flat::RenderType::DrawLine2D_3D);
-        rm.add_color(color);
-        rm.add_start(&start);
-        rm.add_end(&end);
-        self.messages.push(rm.finish());
+        let mut message = RenderMessage::new(
+            flat::RenderType::DrawLine2D_3D,
+            color,
+            flat::Vector3::new(x1, y1, 0.0),
+        );
+        message.end = Some(flat::Vector3::new(x2, y2, z2));
+        self.messages.push(message);
     }
 
     /// Draw text using screen coordinates.
@@ -210,19 +421,16 @@ impl<'a> RenderGroup<'a> {
         (x, y): (f32, f32),
         (scale_x, scale_y): (i32, i32),
         text: impl AsRef<str>,
-        Color(color): Color<'_>,
+        color: Color,
     ) {
-        let start = flat::Vector3::new(x, y, 0.0);
-        let text = self.builder.create_string(text.as_ref());
-
-        let mut rm = flat::RenderMessageBuilder::new(&mut self.builder);
-        rm.add_renderType(flat::RenderType::DrawString2D);
-        rm.add_color(color);
-        rm.add_start(&start);
-        rm.add_scaleX(scale_x);
-        rm.add_scaleY(scale_y);
-        rm.add_text(text);
-        self.messages.push(rm.finish());
+        let mut message = RenderMessage::new(
+            flat::RenderType::DrawString2D,
+            color,
+            flat::Vector3::new(x, y, 0.0),
+        );
+        message.scale = (scale_x, scale_y);
+        message.text = Some(text.as_ref().to_string());
+        self.messages.push(message);
     }
 
     /// Draw text at a point projected from world coordinates to screen
@@ -241,18 +449,143 @@ impl<'a> RenderGroup<'a> {
         (x, y, z): (f32, f32, f32),
         (scale_x, scale_y): (i32, i32),
         text: impl AsRef<str>,
-        Color(color): Color<'_>,
+        color: Color,
+    ) {
+        let mut message = RenderMessage::new(
+            flat::RenderType::DrawString3D,
+            color,
+            flat::Vector3::new(x, y, z),
+        );
+        message.scale = (scale_x, scale_y);
+        message.text = Some(text.as_ref().to_string());
+        self.messages.push(message);
+    }
+
+    /// Draw a rectangle using screen coordinates. `(x, y)` is the top-left
+    /// corner.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::RenderGroup;
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// group.draw_rect_2d((10.0, 10.0), (100, 50), true, green);
+    /// ```
+    pub fn draw_rect_2d(
+        &mut self,
+        (x, y): (f32, f32),
+        size: (i32, i32),
+        filled: bool,
+        color: Color,
+    ) {
+        let start = flat::Vector3::new(x, y, 0.0);
+        self.draw_rect(flat::RenderType::DrawRect2D, start, size, filled, color);
+    }
+
+    /// Draw a rectangle with its top-left corner at a point projected from
+    /// world coordinates to screen coordinates. The size is in pixels.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::RenderGroup;
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// group.draw_rect_3d((10.0, 10.0, 10.0), (20, 20), false, green);
+    /// ```
+    pub fn draw_rect_3d(
+        &mut self,
+        (x, y, z): (f32, f32, f32),
+        size: (i32, i32),
+        filled: bool,
+        color: Color,
     ) {
         let start = flat::Vector3::new(x, y, z);
-        let text = self.builder.create_string(text.as_ref());
+        self.draw_rect(flat::RenderType::DrawRect3D, start, size, filled, color);
+    }
 
-        let mut rm = flat::RenderMessageBuilder::new(&mut self.builder);
-        rm.add_renderType(flat::RenderType::DrawString2D);
-        rm.add_color(color);
-        rm.add_start(&start);
-        rm.add_scaleX(scale_x);
-        rm.add_scaleY(scale_y);
-        rm.add_text(text);
-        self.messages.push(rm.finish());
+    /// Draw a rectangle centered on a point projected from world coordinates
+    /// to screen coordinates. The size is in pixels.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::RenderGroup;
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// group.draw_centered_rect_3d((10.0, 10.0, 10.0), (20, 20), true, green);
+    /// ```
+    pub fn draw_centered_rect_3d(
+        &mut self,
+        (x, y, z): (f32, f32, f32),
+        size: (i32, i32),
+        filled: bool,
+        color: Color,
+    ) {
+        let start = flat::Vector3::new(x, y, z);
+        self.draw_rect(
+            flat::RenderType::DrawCenteredRect3D,
+            start,
+            size,
+            filled,
+            color,
+        );
+    }
+
+    /// The schema has no width or height fields, so like the other RLBot
+    /// languages, we send the size of a rectangle as its scale.
+    fn draw_rect(
+        &mut self,
+        render_type: flat::RenderType,
+        start: flat::Vector3,
+        size: (i32, i32),
+        filled: bool,
+        color: Color,
+    ) {
+        let mut message = RenderMessage::new(render_type, color, start);
+        message.scale = size;
+        message.filled = filled;
+        self.messages.push(message);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::ParseColorError, render::Color};
+
+    #[test]
+    fn hex() {
+        assert_eq!(Color::hex(0x12_34_56), Color::rgb(0x12, 0x34, 0x56));
+        assert_eq!("#123456".parse(), Ok(Color::rgb(0x12, 0x34, 0x56)));
+        assert_eq!("80123456".parse(), Ok(Color::argb(0x80, 0x12, 0x34, 0x56)));
+        assert_eq!("#12345".parse::<Color>(), Err(ParseColorError));
+        assert_eq!("+1234567".parse::<Color>(), Err(ParseColorError));
+        assert_eq!("#12345g".parse::<Color>(), Err(ParseColorError));
+    }
+
+    #[test]
+    fn hsv() {
+        assert_eq!(Color::hsv(0.0, 1.0, 1.0), Color::RED);
+        assert_eq!(Color::hsv(240.0, 1.0, 1.0), Color::BLUE);
+        assert_eq!(Color::hsv(-60.0, 1.0, 1.0), Color::MAGENTA);
+        assert_eq!(Color::hsv(30.0, 1.0, 1.0), Color::ORANGE);
+        assert_eq!(Color::hsv(123.0, 0.0, 0.5), Color::rgb(128, 128, 128));
+        assert_eq!(Color::hsv(0.0, 1.0, 0.0), Color::BLACK);
+    }
+
+    #[test]
+    fn blending() {
+        let half_red = Color::RED.with_alpha(128);
+        assert_eq!(half_red.over(Color::BLUE), Color::rgb(128, 0, 127));
+        assert_eq!(Color::GREEN.over(Color::BLUE), Color::GREEN);
+        assert_eq!(Color::TRANSPARENT.over(Color::BLUE), Color::BLUE);
+        assert_eq!(
+            Color::TRANSPARENT.over(Color::TRANSPARENT),
+            Color::TRANSPARENT
+        );
+
+        assert_eq!(Color::BLACK.lerp(Color::WHITE, 0.5), Color::GRAY);
+        assert_eq!(Color::BLACK.lerp(Color::WHITE, 2.0), Color::WHITE);
     }
 }
@@ -0,0 +1,173 @@
+//! Sending render groups efficiently, tick after tick.
+
+use crate::{
+    error::Error,
+    render::{build_render_group, RenderGroup, RenderMessage},
+    rlbot::RLBot,
+};
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+/// Sends [`RenderGroup`]s on a bot's behalf, working around two costs of
+/// calling [`RenderGroup::render`] directly every tick.
+///
+/// * RLBot rejects render groups that are too large. The manager splits a
+///   group's drawings across as many render groups as it needs to keep each
+///   one under [`DEFAULT_MAX_BYTES`](RenderManager::DEFAULT_MAX_BYTES). The
+///   first uses the group's own id, and the rest use `id + ID_STRIDE`,
+///   `id + 2 * ID_STRIDE`, and so on. If a later frame needs fewer groups, the
+///   leftover ones are cleared.
+/// * Sending a group takes time, even if it's the same as last tick. The
+///   manager remembers a hash of what it last sent for each group, and skips
+///   groups that have not changed.
+///
+/// Keep one `RenderManager` for the life of the bot, since the hashes are what
+/// let it skip work.
+///
+/// # Example
+///
+/// ```no_run
+/// # use rlbot::{Color, RenderManager};
+/// # fn main() -> Result<(), rlbot::Error> {
+/// let rlbot = rlbot::init()?;
+/// let mut renders = RenderManager::new();
+/// let mut packets = rlbot.packeteer();
+/// loop {
+///     let packet = packets.next()?;
+///     let mut group = rlbot.begin_render_group(1234);
+///     for player in &packet.players {
+///         if let Some(hitbox) = &player.hitbox {
+///             group.draw_hitbox_3d(&player.physics, hitbox, Color::GREEN);
+///         }
+///     }
+///     renders.render(group)?;
+/// }
+/// # }
+/// ```
+pub struct RenderManager {
+    max_bytes: usize,
+    /// For each group id we've rendered, the hash of each chunk on screen.
+    sent: HashMap<i32, Vec<u64>>,
+}
+
+impl Default for RenderManager {
+    fn default() -> Self {
+        Self {
+            max_bytes: Self::DEFAULT_MAX_BYTES,
+            sent: HashMap::new(),
+        }
+    }
+}
+
+impl RenderManager {
+    /// The default size, in bytes, of a single render group. This is
+    /// comfortably below the size at which RLBot rejects a group.
+    pub const DEFAULT_MAX_BYTES: usize = 30 * 1024;
+
+    /// The distance between the ids of the render groups that one group is
+    /// split across.
+    pub const ID_STRIDE: i32 = 0x1_0000;
+
+    /// Constructs a new `RenderManager`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the largest render group to send, in bytes. The default is
+    /// [`DEFAULT_MAX_BYTES`](RenderManager::DEFAULT_MAX_BYTES).
+    ///
+    /// A single drawing which is larger than this on its own (e.g. a very long
+    /// string) is still sent, in a group by itself.
+    pub fn max_bytes_per_group(mut self, max_bytes: usize) -> Self {
+        self.max_bytes = max_bytes;
+        self
+    }
+
+    /// Sends a render group, split into as many pieces as needed, skipping
+    /// any piece that is the same as what is already on screen.
+    pub fn render(&mut self, group: RenderGroup<'_>) -> Result<(), Error> {
+        let RenderGroup {
+            rlbot,
+            id,
+            messages,
+        } = group;
+
+        let chunks = split(&messages, self.max_bytes);
+
+        let sent = self.sent.entry(id).or_default();
+        for (index, chunk) in chunks.iter().enumerate() {
+            let builder = build_render_group(chunk_id(id, index), chunk);
+            let data = builder.finished_data();
+            let hash = hash(data);
+            if sent.get(index) == Some(&hash) {
+                continue;
+            }
+            rlbot.interface().render_group(data)?;
+            if index < sent.len() {
+                sent[index] = hash;
+            } else {
+                sent.push(hash);
+            }
+        }
+
+        while sent.len() > chunks.len() {
+            send_empty(rlbot, chunk_id(id, sent.len() - 1))?;
+            sent.pop();
+        }
+        Ok(())
+    }
+
+    /// Clears everything that was rendered with the given group id.
+    pub fn clear(&mut self, rlbot: &RLBot, id: i32) -> Result<(), Error> {
+        let sent = self.sent.entry(id).or_default();
+        // If we've never sent the group, clear it anyway, to be safe.
+        let count = sent.len().max(1);
+        for index in (0..count).rev() {
+            send_empty(rlbot, chunk_id(id, index))?;
+            sent.truncate(index);
+        }
+        Ok(())
+    }
+}
+
+/// Splits messages into chunks whose render groups fit in `max_bytes`. There
+/// is always at least one chunk, so that rendering nothing clears the screen
+/// like `RenderGroup::render` would.
+fn split(messages: &[RenderMessage], max_bytes: usize) -> Vec<&[RenderMessage]> {
+    // The root table, its vtable, and the messages vector's length prefix.
+    const GROUP_SIZE: usize = 64;
+
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut size = GROUP_SIZE;
+    for (i, message) in messages.iter().enumerate() {
+        let message_size = message.max_size();
+        if i > start && size + message_size > max_bytes {
+            chunks.push(&messages[start..i]);
+            start = i;
+            size = GROUP_SIZE;
+        }
+        size += message_size;
+    }
+    chunks.push(&messages[start..]);
+    chunks
+}
+
+fn chunk_id(id: i32, index: usize) -> i32 {
+    id.wrapping_add((index as i32).wrapping_mul(RenderManager::ID_STRIDE))
+}
+
+fn send_empty(rlbot: &RLBot, id: i32) -> Result<(), Error> {
+    let empty: &[RenderMessage] = &[];
+    let builder = build_render_group(id, empty);
+    rlbot.interface().render_group(builder.finished_data())?;
+    Ok(())
+}
+
+fn hash(data: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    hasher.finish()
+}
@@ -0,0 +1,369 @@
+//! Higher-level shapes, drawn as a series of 3D lines.
+
+use crate::{
+    game::{BallPrediction, BoxShape, Physics, Vector3},
+    render::{Color, RenderGroup},
+};
+use std::f32::consts::PI;
+
+impl<'a> RenderGroup<'a> {
+    /// Draw connected lines through a series of points in world coordinates.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::{RenderGroup, Vector3};
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// let points = (0..10).map(|i| Vector3::new(0.0, i as f32 * 100.0, (i * i) as f32));
+    /// group.draw_polyline_3d(points, green);
+    /// ```
+    pub fn draw_polyline_3d(&mut self, points: impl IntoIterator<Item = Vector3>, color: Color) {
+        let mut points = points.into_iter();
+        let mut prev = match points.next() {
+            Some(point) => point,
+            None => return,
+        };
+        for point in points {
+            self.line(prev, point, color);
+            prev = point;
+        }
+    }
+
+    /// Draw a circle in world coordinates, lying in the plane perpendicular to
+    /// `normal`, approximated by `segments` lines.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::{RenderGroup, Vector3};
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// // A circle on the floor around center field.
+    /// let up = Vector3::new(0.0, 0.0, 1.0);
+    /// group.draw_circle_3d(Vector3::new(0.0, 0.0, 0.0), up, 1000.0, 32, green);
+    /// ```
+    pub fn draw_circle_3d(
+        &mut self,
+        center: Vector3,
+        normal: Vector3,
+        radius: f32,
+        segments: usize,
+        color: Color,
+    ) {
+        let u = perpendicular(normal);
+        let v = normal.normalize().cross(u);
+        self.draw_ellipse(center, u * radius, v * radius, segments, color);
+    }
+
+    /// Draw a wireframe sphere in world coordinates, as three circles around
+    /// its axes, each approximated by `segments` lines.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::{RenderGroup, Vector3};
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// group.draw_sphere_3d(Vector3::new(0.0, 0.0, 92.75), 92.75, 16, green);
+    /// ```
+    pub fn draw_sphere_3d(&mut self, center: Vector3, radius: f32, segments: usize, color: Color) {
+        let x = Vector3::new(radius, 0.0, 0.0);
+        let y = Vector3::new(0.0, radius, 0.0);
+        let z = Vector3::new(0.0, 0.0, radius);
+        self.draw_ellipse(center, x, y, segments, color);
+        self.draw_ellipse(center, y, z, segments, color);
+        self.draw_ellipse(center, z, x, segments, color);
+    }
+
+    /// Draw an arrow in world coordinates from `start` to `end`. The head is
+    /// made of four lines of length `head_size`, swept back from the tip.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::{RenderGroup, Vector3};
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// let car = Vector3::new(0.0, 0.0, 17.0);
+    /// let ball = Vector3::new(0.0, 1000.0, 92.75);
+    /// group.draw_arrow_3d(car, ball, 50.0, green);
+    /// ```
+    pub fn draw_arrow_3d(&mut self, start: Vector3, end: Vector3, head_size: f32, color: Color) {
+        self.line(start, end, color);
+
+        let direction = end - start;
+        if direction.length_squared() == 0.0 {
+            return;
+        }
+        let back = -direction.normalize();
+        let u = perpendicular(back);
+        let v = back.cross(u);
+        // Sweep the barbs back at 30 degrees from the shaft.
+        let (sin, cos) = (PI / 6.0).sin_cos();
+        for &side in &[u, v, -u, -v] {
+            self.line(end, end + (back * cos + side * sin) * head_size, color);
+        }
+    }
+
+    /// Draw a car's hitbox as a wireframe box, oriented by the car's
+    /// [`Physics`].
+    ///
+    /// The box is centered on the car's location. See [`BoxShape`] for why
+    /// this is an approximation.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::{GameTickPacket, RenderGroup};
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let packet: GameTickPacket = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// let car = &packet.players[0];
+    /// if let Some(hitbox) = &car.hitbox {
+    ///     group.draw_hitbox_3d(&car.physics, hitbox, green);
+    /// }
+    /// ```
+    pub fn draw_hitbox_3d(&mut self, physics: &Physics, hitbox: &BoxShape, color: Color) {
+        let rotation = physics.rotation_matrix();
+        let forward = rotation.forward * (hitbox.length / 2.0);
+        let right = rotation.right * (hitbox.width / 2.0);
+        let up = rotation.up * (hitbox.height / 2.0);
+        let corner = |f: f32, r: f32, u: f32| physics.location + forward * f + right * r + up * u;
+
+        for &s in &[-1.0, 1.0] {
+            for &t in &[-1.0, 1.0] {
+                // One edge along each axis, through each pair of signs.
+                self.line(corner(-1.0, s, t), corner(1.0, s, t), color);
+                self.line(corner(s, -1.0, t), corner(s, 1.0, t), color);
+                self.line(corner(s, t, -1.0), corner(s, t, 1.0), color);
+            }
+        }
+    }
+
+    /// Draw the ball's predicted path, using at most `segments` lines.
+    ///
+    /// Slices are skipped evenly to fit in the given number of segments, but
+    /// the first and last slices are always included.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # use rlbot::{BallPredictor, GameTickPacket, RenderGroup};
+    /// # let mut group: RenderGroup = unsafe { ::std::mem::uninitialized() };
+    /// # let packet: GameTickPacket = unsafe { ::std::mem::uninitialized() };
+    /// # let green = group.color_rgb(0, 255, 0);
+    /// if let Some(prediction) = BallPredictor::new().predict_packet(&packet) {
+    ///     group.draw_ball_prediction_3d(&prediction, 60, green);
+    /// }
+    /// ```
+    pub fn draw_ball_prediction_3d(
+        &mut self,
+        prediction: &BallPrediction,
+        segments: usize,
+        color: Color,
+    ) {
+        let slices = &prediction.slices;
+        if slices.len() < 2 || segments == 0 {
+            return;
+        }
+        let last = slices.len() - 1;
+        let segments = segments.min(last);
+        let points = (0..=segments).map(|i| slices[i * last / segments].physics.location);
+        self.draw_polyline_3d(points, color);
+    }
+
+    /// Draw the ellipse `center + u cos(t) + v sin(t)`.
+    fn draw_ellipse(
+        &mut self,
+        center: Vector3,
+        u: Vector3,
+        v: Vector3,
+        segments: usize,
+        color: Color,
+    ) {
+        let segments = segments.max(3);
+        let points = (0..=segments).map(|i| {
+            let (sin, cos) = (i as f32 / segments as f32 * 2.0 * PI).sin_cos();
+            center + u * cos + v * sin
+        });
+        self.draw_polyline_3d(points, color);
+    }
+
+    fn line(&mut self, start: Vector3, end: Vector3, color: Color) {
+        self.draw_line_3d((start.x, start.y, start.z), (end.x, end.y, end.z), color);
+    }
+}
+
+/// Returns an arbitrary unit vector perpendicular to `v`.
+fn perpendicular(v: Vector3) -> Vector3 {
+    let v = v.normalize();
+    // Cross with whichever axis is furthest from parallel, to stay accurate.
+    let axis = if v.x.abs() < 0.9 {
+        Vector3::new(1.0, 0.0, 0.0)
+    } else {
+        Vector3::new(0.0, 1.0, 0.0)
+    };
+    v.cross(axis).normalize()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        flat,
+        game::{BallPrediction, BoxShape, Physics, PredictionSlice, Rotator, Vector3},
+        init::init_with_backend,
+        mock::MockBackend,
+        render::RenderGroup,
+    };
+    use std::f32::consts::PI;
+
+    /// Draws into a render group, then returns the endpoints of every line
+    /// that was sent.
+    fn lines(draw: impl FnOnce(&mut RenderGroup<'_>)) -> Vec<(Vector3, Vector3)> {
+        let backend = MockBackend::new();
+        let rlbot = init_with_backend(backend.clone()).unwrap();
+        let mut group = rlbot.begin_render_group(0);
+        draw(&mut group);
+        group.render().unwrap();
+
+        let sent = backend.render_groups();
+        let group = flatbuffers::get_root::<flat::RenderGroup<'_>>(&sent[0]);
+        let messages = group.renderMessages().unwrap();
+        let v = |v: &flat::Vector3| Vector3::new(v.x(), v.y(), v.z());
+        (0..messages.len())
+            .map(|i| messages.get(i))
+            .inspect(|m| assert_eq!(m.renderType(), flat::RenderType::DrawLine3D))
+            .map(|m| (v(m.start().unwrap()), v(m.end().unwrap())))
+            .collect()
+    }
+
+    fn physics(location: Vector3, rotation: Rotator) -> Physics {
+        Physics {
+            location,
+            rotation,
+            velocity: Vector3::default(),
+            angular_velocity: Vector3::default(),
+            _non_exhaustive: (),
+        }
+    }
+
+    #[test]
+    fn polyline() {
+        let points = vec![
+            Vector3::new(0.0, 0.0, 0.0),
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(1.0, 1.0, 0.0),
+        ];
+        let lines = lines(|g| {
+            let color = g.color_rgb(0, 0, 0);
+            g.draw_polyline_3d(points.clone(), color);
+            g.draw_polyline_3d(vec![Vector3::default()], color);
+        });
+        assert_eq!(lines, vec![(points[0], points[1]), (points[1], points[2])]);
+    }
+
+    #[test]
+    fn circle() {
+        let center = Vector3::new(100.0, 200.0, 300.0);
+        let normal = Vector3::new(1.0, 1.0, 0.0);
+        let lines = lines(|g| {
+            let color = g.color_rgb(0, 0, 0);
+            g.draw_circle_3d(center, normal, 50.0, 12, color);
+        });
+        assert_eq!(lines.len(), 12);
+        for (start, end) in &lines {
+            assert!(((*start - center).length() - 50.0).abs() < 1e-3);
+            assert!((*start - center).dot(normal).abs() < 1e-3);
+            let chord = 2.0 * 50.0 * (PI / 12.0).sin();
+            assert!((start.distance(*end) - chord).abs() < 1e-3);
+        }
+        // The circle is closed.
+        assert!(lines[0].0.distance(lines[11].1) < 1e-3);
+    }
+
+    #[test]
+    fn sphere() {
+        let center = Vector3::new(0.0, 0.0, 92.75);
+        let lines = lines(|g| {
+            let color = g.color_rgb(0, 0, 0);
+            g.draw_sphere_3d(center, 92.75, 8, color);
+        });
+        assert_eq!(lines.len(), 24);
+        for (start, _) in &lines {
+            assert!(((*start - center).length() - 92.75).abs() < 1e-3);
+        }
+    }
+
+    #[test]
+    fn arrow() {
+        let start = Vector3::new(0.0, 0.0, 0.0);
+        let end = Vector3::new(0.0, 100.0, 0.0);
+        let lines = lines(|g| {
+            let color = g.color_rgb(0, 0, 0);
+            g.draw_arrow_3d(start, end, 10.0, color);
+        });
+        assert_eq!(lines[0], (start, end));
+        assert_eq!(lines.len(), 5);
+        for (tip, barb) in &lines[1..] {
+            assert_eq!(*tip, end);
+            assert!((tip.distance(*barb) - 10.0).abs() < 1e-3);
+            // Barbs point backwards.
+            assert!(barb.y < end.y);
+        }
+    }
+
+    #[test]
+    fn hitbox() {
+        let hitbox = BoxShape {
+            length: 118.0,
+            width: 84.2,
+            height: 36.2,
+            _non_exhaustive: (),
+        };
+        let location = Vector3::new(0.0, 0.0, 17.0);
+        let car = physics(location, Rotator::new(0.0, PI / 2.0, 0.0));
+        let lines = lines(|g| {
+            let color = g.color_rgb(0, 0, 0);
+            g.draw_hitbox_3d(&car, &hitbox, color);
+        });
+        assert_eq!(lines.len(), 12);
+
+        let mut lengths = lines
+            .iter()
+            .map(|(start, end)| start.distance(*end))
+            .collect::<Vec<_>>();
+        lengths.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        for (i, &expected) in [36.2, 84.2, 118.0].iter().enumerate() {
+            for length in &lengths[i * 4..i * 4 + 4] {
+                assert!((length - expected).abs() < 1e-3);
+            }
+        }
+        // The car faces +y, so its length runs along y.
+        let long = lines
+            .iter()
+            .find(|(start, end)| (start.distance(*end) - 118.0).abs() < 1e-3)
+            .unwrap();
+        assert!((long.1.y - long.0.y).abs() > 117.0);
+    }
+
+    #[test]
+    fn ball_prediction() {
+        let prediction = BallPrediction {
+            slices: (0..=6)
+                .map(|i| PredictionSlice {
+                    game_seconds: i as f32,
+                    physics: physics(Vector3::new(i as f32, 0.0, 0.0), Rotator::default()),
+                    _non_exhaustive: (),
+                })
+                .collect(),
+            _non_exhaustive: (),
+        };
+        let lines = lines(|g| {
+            let color = g.color_rgb(0, 0, 0);
+            g.draw_ball_prediction_3d(&prediction, 3, color);
+        });
+        let xs = lines.iter().map(|(a, b)| (a.x, b.x)).collect::<Vec<_>>();
+        assert_eq!(xs, vec![(0.0, 2.0), (2.0, 4.0), (4.0, 6.0)]);
+    }
+}
@@ -0,0 +1,636 @@
+//! Recording game data to disk, and playing it back later.
+
+use crate::{
+    backend::CoreBackend,
+    ffi::{self, RLBotCoreStatus},
+    mock::StickyQueue,
+};
+use std::{
+    convert::TryInto,
+    fs::File,
+    io::{self, Read, Write},
+    path::Path,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+const MAGIC: &[u8; 8] = b"RLBOTREC";
+const VERSION: u32 = 1;
+const FRAME_HEADER_LEN: usize = 1 + 8 + 4;
+
+/// The kinds of flatbuffer that are captured in a recording.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+enum FrameKind {
+    FieldInfo = 0,
+    LiveDataPacket = 1,
+    RigidBodyTick = 2,
+    BallPrediction = 3,
+    QuickChatMessages = 4,
+}
+
+impl FrameKind {
+    const COUNT: usize = 5;
+
+    fn from_u8(x: u8) -> Option<Self> {
+        match x {
+            0 => Some(FrameKind::FieldInfo),
+            1 => Some(FrameKind::LiveDataPacket),
+            2 => Some(FrameKind::RigidBodyTick),
+            3 => Some(FrameKind::BallPrediction),
+            4 => Some(FrameKind::QuickChatMessages),
+            _ => None,
+        }
+    }
+}
+
+/// A [`CoreBackend`] that passes everything through to another backend, and
+/// also writes every flatbuffer it receives to a recording.
+///
+/// The recording can be played back with [`ReplayBackend`].
+///
+/// Each frame is tagged with the time it was received, relative to when the
+/// `RecordingBackend` was created. A buffer which is identical to the previous
+/// buffer of the same kind is not written again, so polling quickly does not
+/// bloat the file. Every frame is written with a single call to `write_all`,
+/// so an unbuffered [`File`] keeps a usable recording even if the process is
+/// killed.
+///
+/// If writing fails, recording stops, but calls keep passing through to the
+/// inner backend. The error can be retrieved with
+/// [`take_error`](RecordingBackend::take_error).
+///
+/// Most of the time you'll want
+/// [`InitOptions::record_to`](crate::InitOptions::record_to) instead of using
+/// this directly.
+pub struct RecordingBackend<B, W> {
+    inner: B,
+    recorder: Mutex<Recorder<W>>,
+}
+
+struct Recorder<W> {
+    writer: Option<W>,
+    error: Option<io::Error>,
+    start: Instant,
+    last: [Option<Vec<u8>>; FrameKind::COUNT],
+}
+
+impl<B: CoreBackend, W: Write + Send> RecordingBackend<B, W> {
+    /// Wraps `inner`, recording its output to `writer`.
+    pub fn new(inner: B, mut writer: W) -> io::Result<Self> {
+        writer.write_all(MAGIC)?;
+        writer.write_all(&VERSION.to_le_bytes())?;
+
+        Ok(Self {
+            inner,
+            recorder: Mutex::new(Recorder {
+                writer: Some(writer),
+                error: None,
+                start: Instant::now(),
+                last: Default::default(),
+            }),
+        })
+    }
+
+    /// Returns the error that stopped the recording, if writing has failed
+    /// since the last call.
+    pub fn take_error(&self) -> Option<io::Error> {
+        self.recorder.lock().unwrap().error.take()
+    }
+
+    fn tee(&self, kind: FrameKind, buffer: Option<Vec<u8>>) -> Option<Vec<u8>> {
+        if let Some(buffer) = &buffer {
+            self.recorder.lock().unwrap().record(kind, buffer);
+        }
+        buffer
+    }
+}
+
+impl<W: Write> Recorder<W> {
+    fn record(&mut self, kind: FrameKind, buffer: &[u8]) {
+        let last = &mut self.last[kind as usize];
+        if last.as_ref().map(|x| &x[..]) == Some(buffer) {
+            return;
+        }
+        *last = Some(buffer.to_vec());
+
+        if let Some(writer) = &mut self.writer {
+            let timestamp = self.start.elapsed().as_micros() as u64;
+            let mut frame = Vec::with_capacity(FRAME_HEADER_LEN + buffer.len());
+            frame.push(kind as u8);
+            frame.extend_from_slice(&timestamp.to_le_bytes());
+            frame.extend_from_slice(&(buffer.len() as u32).to_le_bytes());
+            frame.extend_from_slice(buffer);
+
+            if let Err(err) = writer.write_all(&frame) {
+                self.writer = None;
+                self.error = Some(err);
+            }
+        }
+    }
+}
+
+impl<B: CoreBackend, W: Write + Send> CoreBackend for RecordingBackend<B, W> {
+    fn is_initialized(&self) -> bool {
+        self.inner.is_initialized()
+    }
+
+    fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.tee(
+            FrameKind::FieldInfo,
+            self.inner.update_field_info_flatbuffer(),
+        )
+    }
+
+    fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.tee(
+            FrameKind::LiveDataPacket,
+            self.inner.update_live_data_packet_flatbuffer(),
+        )
+    }
+
+    fn update_live_data_packet_flatbuffer_into(&self, buf: &mut Vec<u8>) -> bool {
+        if !self.inner.update_live_data_packet_flatbuffer_into(buf) {
+            return false;
+        }
+        let mut recorder = self.recorder.lock().unwrap();
+        recorder.record(FrameKind::LiveDataPacket, buf);
+        true
+    }
+
+    fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.tee(
+            FrameKind::RigidBodyTick,
+            self.inner.update_rigid_body_tick_flatbuffer(),
+        )
+    }
+
+    fn get_ball_prediction(&self) -> Option<Vec<u8>> {
+        self.tee(FrameKind::BallPrediction, self.inner.get_ball_prediction())
+    }
+
+    fn set_game_state(&self, desired_game_state_buffer: &[u8]) -> RLBotCoreStatus {
+        self.inner.set_game_state(desired_game_state_buffer)
+    }
+
+    fn start_match_flatbuffer(&self, match_settings_buffer: &[u8]) -> RLBotCoreStatus {
+        self.inner.start_match_flatbuffer(match_settings_buffer)
+    }
+
+    fn send_quick_chat(&self, quick_chat_buffer: &[u8]) -> RLBotCoreStatus {
+        self.inner.send_quick_chat(quick_chat_buffer)
+    }
+
+    fn receive_chat(
+        &self,
+        player_index: i32,
+        team: i32,
+        last_message_index: i32,
+    ) -> Option<Vec<u8>> {
+        self.tee(
+            FrameKind::QuickChatMessages,
+            self.inner
+                .receive_chat(player_index, team, last_message_index),
+        )
+    }
+
+    fn update_player_input_flatbuffer(&self, player_input_buffer: &[u8]) -> RLBotCoreStatus {
+        self.inner
+            .update_player_input_flatbuffer(player_input_buffer)
+    }
+
+    fn render_group(&self, render_group_buffer: &[u8]) -> RLBotCoreStatus {
+        self.inner.render_group(render_group_buffer)
+    }
+
+    // The struct-based calls are passed through, but not recorded.
+
+    fn update_field_info(&self, field_info: &mut ffi::FieldInfo) -> RLBotCoreStatus {
+        self.inner.update_field_info(field_info)
+    }
+
+    fn update_live_data_packet(&self, packet: &mut ffi::LiveDataPacket) -> RLBotCoreStatus {
+        self.inner.update_live_data_packet(packet)
+    }
+
+    fn update_rigid_body_tick(&self, tick: &mut ffi::RigidBodyTick) -> RLBotCoreStatus {
+        self.inner.update_rigid_body_tick(tick)
+    }
+
+    fn start_match(&self, match_settings: ffi::MatchSettings) -> RLBotCoreStatus {
+        self.inner.start_match(match_settings)
+    }
+
+    fn send_chat(
+        &self,
+        quick_chat_preset: ffi::QuickChatPreset,
+        player_index: i32,
+        team_only: bool,
+    ) -> RLBotCoreStatus {
+        self.inner
+            .send_chat(quick_chat_preset, player_index, team_only)
+    }
+
+    fn update_player_input(
+        &self,
+        player_input: ffi::PlayerInput,
+        player_index: i32,
+    ) -> RLBotCoreStatus {
+        self.inner.update_player_input(player_input, player_index)
+    }
+
+    fn get_ball_prediction_struct(
+        &self,
+        result: &mut ffi::BallPredictionPacket,
+    ) -> RLBotCoreStatus {
+        self.inner.get_ball_prediction_struct(result)
+    }
+}
+
+/// Controls how quickly a [`ReplayBackend`] plays back its recording.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Frames become available with the same timing as when they were
+    /// recorded. If the caller polls too slowly, frames are skipped, just like
+    /// with the real game.
+    RealTime,
+    /// Every call returns the next frame of its kind, without waiting. No
+    /// frames are ever skipped. Use this for batch analysis.
+    AsFastAsPossible,
+}
+
+/// A [`CoreBackend`] that plays back a recording made by a
+/// [`RecordingBackend`].
+///
+/// Game data is fed through exactly as it was recorded, so [`Packeteer`] and
+/// [`Physicist`] work as usual. Once the recording runs out, the last frame of
+/// each kind keeps being returned, which looks like a frozen game.
+///
+/// Outgoing calls (player input, rendering, etc.) are accepted and ignored.
+///
+/// # Example
+///
+/// ```no_run
+/// # fn main() -> Result<(), Box<::std::error::Error>> {
+/// use rlbot::{ReplayBackend, ReplayMode};
+///
+/// let backend = ReplayBackend::open("match.rlbotrec", ReplayMode::AsFastAsPossible)?;
+/// let rlbot = rlbot::init_with_backend(backend)?;
+/// let mut packets = rlbot.packeteer();
+/// loop {
+///     let packet = packets.next()?;
+///     // ...
+/// }
+/// # }
+/// ```
+///
+/// [`Packeteer`]: crate::Packeteer
+/// [`Physicist`]: crate::Physicist
+pub struct ReplayBackend {
+    mode: ReplayMode,
+    state: Mutex<ReplayState>,
+}
+
+struct ReplayState {
+    frames: [StickyQueue<(Duration, Vec<u8>)>; FrameKind::COUNT],
+    first_timestamp: Duration,
+    start: Option<Instant>,
+}
+
+impl ReplayBackend {
+    /// Opens a recording file.
+    pub fn open(path: impl AsRef<Path>, mode: ReplayMode) -> io::Result<Self> {
+        Self::from_reader(File::open(path)?, mode)
+    }
+
+    /// Reads a recording from any reader.
+    ///
+    /// A truncated final frame, as left behind by a process that was killed
+    /// mid-write, is ignored.
+    pub fn from_reader(mut reader: impl Read, mode: ReplayMode) -> io::Result<Self> {
+        let mut data = Vec::new();
+        reader.read_to_end(&mut data)?;
+
+        if data.len() < MAGIC.len() + 4 || &data[..MAGIC.len()] != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not an RLBot recording",
+            ));
+        }
+        let version = u32::from_le_bytes(data[MAGIC.len()..MAGIC.len() + 4].try_into().unwrap());
+        if version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported recording version {}", version),
+            ));
+        }
+
+        let mut frames: [StickyQueue<_>; FrameKind::COUNT] = Default::default();
+        let mut first_timestamp = None;
+        let mut rest = &data[MAGIC.len() + 4..];
+        while rest.len() >= FRAME_HEADER_LEN {
+            let kind = FrameKind::from_u8(rest[0])
+                .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "unknown frame kind"))?;
+            let timestamp = u64::from_le_bytes(rest[1..9].try_into().unwrap());
+            let len = u32::from_le_bytes(rest[9..13].try_into().unwrap()) as usize;
+            rest = &rest[FRAME_HEADER_LEN..];
+            if rest.len() < len {
+                break;
+            }
+
+            let timestamp = Duration::from_micros(timestamp);
+            first_timestamp.get_or_insert(timestamp);
+            frames[kind as usize].push((timestamp, rest[..len].to_vec()));
+            rest = &rest[len..];
+        }
+
+        Ok(Self {
+            mode,
+            state: Mutex::new(ReplayState {
+                frames,
+                first_timestamp: first_timestamp.unwrap_or_default(),
+                start: None,
+            }),
+        })
+    }
+
+    fn next(&self, kind: FrameKind) -> Option<Vec<u8>> {
+        let mut state = self.state.lock().unwrap();
+        let now = match self.mode {
+            ReplayMode::RealTime => {
+                let start = *state.start.get_or_insert_with(Instant::now);
+                Some(state.first_timestamp + start.elapsed())
+            }
+            ReplayMode::AsFastAsPossible => None,
+        };
+
+        let queue = &mut state.frames[kind as usize];
+        match now {
+            Some(now) => queue.next_while(|(ts, _)| *ts <= now),
+            None => queue.next(),
+        }
+        .map(|(_, buf)| buf)
+    }
+}
+
+impl CoreBackend for ReplayBackend {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+
+    fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.next(FrameKind::FieldInfo)
+    }
+
+    fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.next(FrameKind::LiveDataPacket)
+    }
+
+    fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
+        self.next(FrameKind::RigidBodyTick)
+    }
+
+    fn get_ball_prediction(&self) -> Option<Vec<u8>> {
+        self.next(FrameKind::BallPrediction)
+    }
+
+    fn set_game_state(&self, _desired_game_state_buffer: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+
+    fn start_match_flatbuffer(&self, _match_settings_buffer: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+
+    fn send_quick_chat(&self, _quick_chat_buffer: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+
+    /// Returns the recorded chats as-is, whatever player and message index are
+    /// asked for. [`QuickChatInbox`](crate::QuickChatInbox) skips any chats it
+    /// has already seen.
+    fn receive_chat(
+        &self,
+        _player_index: i32,
+        _team: i32,
+        _last_message_index: i32,
+    ) -> Option<Vec<u8>> {
+        self.next(FrameKind::QuickChatMessages)
+    }
+
+    fn update_player_input_flatbuffer(&self, _player_input_buffer: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+
+    fn render_group(&self, _render_group_buffer: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        backend::CoreBackend,
+        ffi::{self, RLBotCoreStatus},
+        mock::MockBackend,
+        replay::{RecordingBackend, ReplayBackend, ReplayMode},
+    };
+    use std::{io, thread::sleep, time::Duration};
+
+    fn record(f: impl FnOnce(&MockBackend, &dyn CoreBackend)) -> Vec<u8> {
+        let mut data = Vec::new();
+        let mock = MockBackend::new();
+        let recorder = RecordingBackend::new(mock.clone(), &mut data).unwrap();
+        f(&mock, &recorder);
+        drop(recorder);
+        data
+    }
+
+    #[test]
+    fn duplicates_are_not_recorded() {
+        let data = record(|mock, recorder| {
+            mock.push_live_data_packet(vec![1]);
+            recorder.update_live_data_packet_flatbuffer();
+            recorder.update_live_data_packet_flatbuffer();
+            mock.push_live_data_packet(vec![2]);
+            recorder.update_live_data_packet_flatbuffer();
+        });
+
+        let replay = ReplayBackend::from_reader(&data[..], ReplayMode::AsFastAsPossible).unwrap();
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![1]));
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![2]));
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![2]));
+    }
+
+    #[test]
+    fn kinds_are_kept_apart() {
+        let data = record(|mock, recorder| {
+            mock.push_rigid_body_tick(vec![1]);
+            mock.push_ball_prediction(vec![2]);
+            mock.push_field_info(vec![3]);
+            mock.push_quick_chat_messages(vec![4]);
+            recorder.update_rigid_body_tick_flatbuffer();
+            recorder.get_ball_prediction();
+            recorder.update_field_info_flatbuffer();
+            recorder.receive_chat(0, 0, -1);
+        });
+
+        let replay = ReplayBackend::from_reader(&data[..], ReplayMode::AsFastAsPossible).unwrap();
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), None);
+        assert_eq!(replay.update_rigid_body_tick_flatbuffer(), Some(vec![1]));
+        assert_eq!(replay.get_ball_prediction(), Some(vec![2]));
+        assert_eq!(replay.update_field_info_flatbuffer(), Some(vec![3]));
+        assert_eq!(replay.receive_chat(0, 0, -1), Some(vec![4]));
+    }
+
+    #[test]
+    fn real_time_keeps_timing() {
+        let data = record(|mock, recorder| {
+            mock.push_live_data_packet(vec![1]);
+            recorder.update_live_data_packet_flatbuffer();
+            sleep(Duration::from_millis(100));
+            mock.push_live_data_packet(vec![2]);
+            recorder.update_live_data_packet_flatbuffer();
+        });
+
+        let replay = ReplayBackend::from_reader(&data[..], ReplayMode::RealTime).unwrap();
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![1]));
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![1]));
+        sleep(Duration::from_millis(150));
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![2]));
+    }
+
+    #[test]
+    fn truncated_frame_is_ignored() {
+        let mut data = record(|mock, recorder| {
+            mock.push_live_data_packet(vec![1]);
+            recorder.update_live_data_packet_flatbuffer();
+            mock.push_live_data_packet(vec![2, 2, 2]);
+            recorder.update_live_data_packet_flatbuffer();
+        });
+        data.pop();
+
+        let replay = ReplayBackend::from_reader(&data[..], ReplayMode::AsFastAsPossible).unwrap();
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![1]));
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![1]));
+    }
+
+    #[test]
+    fn bad_magic() {
+        let result = ReplayBackend::from_reader(&b"not a recording"[..], ReplayMode::RealTime);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn write_error_is_kept() {
+        // Room for the header, but not for any frames.
+        let mut data = [0; 12];
+        let mock = MockBackend::new();
+        let recorder = RecordingBackend::new(mock.clone(), &mut data[..]).unwrap();
+        mock.push_live_data_packet(vec![1]);
+        assert_eq!(recorder.update_live_data_packet_flatbuffer(), Some(vec![1]));
+        assert_eq!(
+            recorder.take_error().map(|e| e.kind()),
+            Some(io::ErrorKind::WriteZero),
+        );
+        assert!(recorder.take_error().is_none());
+
+        // Calls still pass through after recording stops.
+        mock.push_live_data_packet(vec![2]);
+        assert_eq!(recorder.update_live_data_packet_flatbuffer(), Some(vec![2]));
+        assert!(recorder.take_error().is_none());
+    }
+
+    /// Only supports the struct-based calls, like an old DLL, plus the
+    /// zero-copy packet call, so tests can tell it apart from the copying one.
+    struct StructBackend;
+
+    impl CoreBackend for StructBackend {
+        fn is_initialized(&self) -> bool {
+            true
+        }
+        fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
+            None
+        }
+        fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
+            None
+        }
+        fn update_live_data_packet_flatbuffer_into(&self, buf: &mut Vec<u8>) -> bool {
+            buf.clear();
+            buf.push(7);
+            true
+        }
+        fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
+            None
+        }
+        fn get_ball_prediction(&self) -> Option<Vec<u8>> {
+            None
+        }
+        fn set_game_state(&self, _: &[u8]) -> RLBotCoreStatus {
+            RLBotCoreStatus::NotInitialized
+        }
+        fn start_match_flatbuffer(&self, _: &[u8]) -> RLBotCoreStatus {
+            RLBotCoreStatus::NotInitialized
+        }
+        fn send_quick_chat(&self, _: &[u8]) -> RLBotCoreStatus {
+            RLBotCoreStatus::NotInitialized
+        }
+        fn update_player_input_flatbuffer(&self, _: &[u8]) -> RLBotCoreStatus {
+            RLBotCoreStatus::NotInitialized
+        }
+        fn render_group(&self, _: &[u8]) -> RLBotCoreStatus {
+            RLBotCoreStatus::NotInitialized
+        }
+        fn update_field_info(&self, _: &mut ffi::FieldInfo) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+        fn update_live_data_packet(&self, _: &mut ffi::LiveDataPacket) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+        fn update_rigid_body_tick(&self, _: &mut ffi::RigidBodyTick) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+        fn start_match(&self, _: ffi::MatchSettings) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+        fn send_chat(&self, _: ffi::QuickChatPreset, _: i32, _: bool) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+        fn update_player_input(&self, _: ffi::PlayerInput, _: i32) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+        fn get_ball_prediction_struct(&self, _: &mut ffi::BallPredictionPacket) -> RLBotCoreStatus {
+            RLBotCoreStatus::Success
+        }
+    }
+
+    #[test]
+    fn struct_calls_pass_through() {
+        let mut data = Vec::new();
+        let recorder = RecordingBackend::new(StructBackend, &mut data).unwrap();
+        let statuses = [
+            recorder.update_field_info(&mut Default::default()),
+            recorder.update_live_data_packet(&mut Default::default()),
+            recorder.update_rigid_body_tick(&mut Default::default()),
+            recorder.start_match(Default::default()),
+            recorder.send_chat(ffi::QuickChatPreset::Information_IGotIt, 0, false),
+            recorder.update_player_input(Default::default(), 0),
+            recorder.get_ball_prediction_struct(&mut Default::default()),
+        ];
+        for status in &statuses {
+            assert_eq!(*status, RLBotCoreStatus::Success);
+        }
+    }
+
+    #[test]
+    fn packet_into_is_forwarded_and_recorded() {
+        let mut data = Vec::new();
+        let recorder = RecordingBackend::new(StructBackend, &mut data).unwrap();
+        let mut buf = Vec::new();
+        assert!(recorder.update_live_data_packet_flatbuffer_into(&mut buf));
+        assert_eq!(buf, vec![7]);
+        drop(recorder);
+
+        let replay = ReplayBackend::from_reader(&data[..], ReplayMode::AsFastAsPossible).unwrap();
+        assert_eq!(replay.update_live_data_packet_flatbuffer(), Some(vec![7]));
+    }
+}
@@ -1,19 +1,26 @@
 use crate::{
-    error::RLBotError,
+    error::Error,
     game::{build_update_player_input, ControllerState},
     interface::RLBotInterface,
     match_settings::MatchSettings,
-    packeteer::Packeteer,
+    packeteer::{Packeteer, PacketeerOptions},
     physicist::Physicist,
+    quick_chat::{build_quick_chat, QuickChatInbox, QuickChatLimiter, QuickChatSelection},
     render::RenderGroup,
     state,
 };
-use std::{borrow::Borrow, cell::Cell, error::Error, marker::PhantomData};
+use std::{
+    borrow::Borrow,
+    cell::{Cell, RefCell},
+    marker::PhantomData,
+    time::Instant,
+};
 
 /// The low-level interface to RLBot. All RLBot calls that are available can be
 /// made through this struct.
 pub struct RLBot {
     interface: RLBotInterface,
+    quick_chat_limiter: RefCell<QuickChatLimiter>,
     /// I strongly doubt the RLBot DLL is thread-safe, so let's enforce that
     /// restriction.
     ///
@@ -25,6 +32,7 @@ impl RLBot {
     pub(crate) fn new(interface: RLBotInterface) -> Self {
         Self {
             interface,
+            quick_chat_limiter: RefCell::new(QuickChatLimiter::default()),
             not_sync: PhantomData,
         }
     }
@@ -37,7 +45,13 @@ impl RLBot {
     /// Returns a [`Packeteer`] object, for conveniently accessing game state
     /// as it occurs.
     pub fn packeteer(&self) -> Packeteer<'_> {
-        Packeteer::new(self)
+        self.packeteer_with(PacketeerOptions::default())
+    }
+
+    /// Returns a [`Packeteer`] object which waits for packets according to the
+    /// given [`PacketeerOptions`].
+    pub fn packeteer_with(&self, options: PacketeerOptions) -> Packeteer<'_> {
+        Packeteer::new(self, options)
     }
 
     /// Returns a [`Physicist`] object, for conveniently accessing physics
@@ -46,15 +60,23 @@ impl RLBot {
         Physicist::new(self)
     }
 
+    /// Returns a [`QuickChatInbox`] object, for receiving the quick chats that
+    /// the given player can see as they are sent. `team` is the player's team,
+    /// and is used to hide the other team's team-only chats.
+    pub fn quick_chat_inbox(&self, player_index: i32, team: i32) -> QuickChatInbox<'_> {
+        QuickChatInbox::new(self, player_index, team)
+    }
+
     /// Sends player input to RLBot.
     pub fn update_player_input(
         &self,
         player_index: i32,
         controller_state: &ControllerState,
-    ) -> Result<(), RLBotError> {
+    ) -> Result<(), Error> {
         let built = build_update_player_input(player_index, controller_state);
         self.interface
-            .update_player_input_flatbuffer(built.finished_data())
+            .update_player_input_flatbuffer(built.finished_data())?;
+        Ok(())
     }
 
     /// Sends multiple player inputs to RLBot.
@@ -63,21 +85,59 @@ impl RLBot {
     pub fn update_multiple_inputs(
         &self,
         mut inputs: impl Iterator<Item = (i32, impl Borrow<ControllerState>)>,
-    ) -> Result<(), RLBotError> {
+    ) -> Result<(), Error> {
         inputs.try_for_each(|(index, input)| self.update_player_input(index, input.borrow()))
     }
 
+    /// Sends a quick chat on behalf of a player.
+    ///
+    /// # Errors
+    ///
+    /// Like the RLBot framework, this allows each player at most five chats
+    /// within a two second window. Chats beyond that are dropped, and
+    /// [`Error::QuickChatRateLimited`] is returned.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<::std::error::Error>> {
+    /// let rlbot = rlbot::init()?;
+    /// rlbot.send_quick_chat(0, rlbot::QuickChatSelection::Information_IGotIt, true)?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn send_quick_chat(
+        &self,
+        player_index: i32,
+        selection: QuickChatSelection,
+        team_only: bool,
+    ) -> Result<(), Error> {
+        let now = Instant::now();
+        self.quick_chat_limiter
+            .borrow_mut()
+            .check(player_index, now)
+            .map_err(Error::QuickChatRateLimited)?;
+        let built = build_quick_chat(player_index, selection, team_only);
+        self.interface.send_quick_chat(built.finished_data())?;
+        // Only chats that made it through count against the limit.
+        self.quick_chat_limiter
+            .borrow_mut()
+            .record(player_index, now);
+        Ok(())
+    }
+
     /// Sets the game state.
     pub fn set_game_state(
         &self,
         desired_game_state: &state::DesiredGameState,
-    ) -> Result<(), RLBotError> {
+    ) -> Result<(), Error> {
         let buffer = desired_game_state.serialize();
-        self.interface.set_game_state(buffer.finished_data())
+        self.interface.set_game_state(buffer.finished_data())?;
+        Ok(())
     }
 
     /// Tells RLBot to start a match.
-    pub fn start_match(&self, match_settings: &MatchSettings<'_>) -> Result<(), Box<dyn Error>> {
+    pub fn start_match(&self, match_settings: &MatchSettings) -> Result<(), Error> {
         let buffer = match_settings.build();
         self.interface
             .start_match_flatbuffer(buffer.finished_data())?;
@@ -87,7 +147,7 @@ impl RLBot {
     /// Spin-waits until a match is active.
     ///
     /// Call `start_match` before calling this method.
-    pub fn wait_for_match_start(&self) -> Result<(), Box<dyn Error>> {
+    pub fn wait_for_match_start(&self) -> Result<(), Error> {
         let mut packets = self.packeteer();
         let mut count = 0;
 
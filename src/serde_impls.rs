@@ -0,0 +1,208 @@
+//! `Serialize` and `Deserialize` for the flatbuffer enums used in
+//! [`MatchSettings`](crate::MatchSettings).
+//!
+//! These enums are generated code, so they can't simply derive the traits.
+//! Each variant is written as its name in the flatbuffer schema, e.g.
+//! `"Five_Minutes"`, which stays stable across versions of this crate.
+
+use crate::flat::{
+    BallBouncinessOption, BallMaxSpeedOption, BallSizeOption, BallTypeOption, BallWeightOption,
+    BoostOption, BoostStrengthOption, DemolishOption, ExistingMatchBehavior, GameMap, GameMode,
+    GameSpeedOption, GravityOption, MatchLength, MaxScore, OvertimeOption, RespawnTimeOption,
+    RumbleOption, SeriesLengthOption,
+};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
+macro_rules! named_enum {
+    ($ty:ident { $($variant:ident),* $(,)* }) => {
+        impl Serialize for $ty {
+            fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(match self {
+                    $($ty::$variant => stringify!($variant),)*
+                })
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $ty {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                const VARIANTS: &[&str] = &[$(stringify!($variant)),*];
+                let name = String::deserialize(deserializer)?;
+                match name.as_str() {
+                    $(stringify!($variant) => Ok($ty::$variant),)*
+                    _ => Err(de::Error::unknown_variant(&name, VARIANTS)),
+                }
+            }
+        }
+    };
+}
+
+named_enum!(GameMode {
+    Soccer,
+    Hoops,
+    Dropshot,
+    Hockey,
+    Rumble,
+});
+
+named_enum!(GameMap {
+    DFHStadium,
+    Mannfield,
+    ChampionsField,
+    UrbanCentral,
+    BeckwithPark,
+    UtopiaColiseum,
+    Wasteland,
+    NeoTokyo,
+    AquaDome,
+    StarbaseArc,
+    Farmstead,
+    SaltyShores,
+    DFHStadium_Stormy,
+    DFHStadium_Day,
+    Mannfield_Stormy,
+    Mannfield_Night,
+    ChampionsField_Day,
+    BeckwithPark_Stormy,
+    BeckwithPark_Midnight,
+    UrbanCentral_Night,
+    UrbanCentral_Dawn,
+    UtopiaColiseum_Dusk,
+    DFHStadium_Snowy,
+    Mannfield_Snowy,
+    UtopiaColiseum_Snowy,
+    Badlands,
+    Badlands_Night,
+    TokyoUnderpass,
+    Arctagon,
+    Pillars,
+    Cosmic,
+    DoubleGoal,
+    Octagon,
+    Underpass,
+    UtopiaRetro,
+    Hoops_DunkHouse,
+    DropShot_Core707,
+    ThrowbackStadium,
+});
+
+named_enum!(MatchLength {
+    Five_Minutes,
+    Ten_Minutes,
+    Twenty_Minutes,
+    Unlimited,
+});
+
+named_enum!(MaxScore {
+    Unlimited,
+    One_Goal,
+    Three_Goals,
+    Five_Goals,
+});
+
+named_enum!(OvertimeOption {
+    Unlimited,
+    Five_Max_First_Score,
+    Five_Max_Random_Team,
+});
+
+named_enum!(SeriesLengthOption {
+    Unlimited,
+    Three_Games,
+    Five_Games,
+    Seven_Games,
+});
+
+named_enum!(GameSpeedOption {
+    Default,
+    Slo_Mo,
+    Time_Warp,
+});
+
+named_enum!(BallMaxSpeedOption {
+    Default,
+    Slow,
+    Fast,
+    Super_Fast,
+});
+
+named_enum!(BallTypeOption {
+    Default,
+    Cube,
+    Puck,
+    Basketball,
+});
+
+named_enum!(BallWeightOption {
+    Default,
+    Light,
+    Heavy,
+    Super_Light,
+});
+
+named_enum!(BallSizeOption {
+    Default,
+    Small,
+    Large,
+    Gigantic,
+});
+
+named_enum!(BallBouncinessOption {
+    Default,
+    Low,
+    High,
+    Super_High,
+});
+
+named_enum!(BoostOption {
+    Normal_Boost,
+    Unlimited_Boost,
+    Slow_Recharge,
+    Rapid_Recharge,
+    No_Boost,
+});
+
+named_enum!(RumbleOption {
+    No_Rumble,
+    Default,
+    Slow,
+    Civilized,
+    Destruction_Derby,
+    Spring_Loaded,
+    Spikes_Only,
+    Spike_Rush,
+});
+
+named_enum!(BoostStrengthOption {
+    One,
+    OneAndAHalf,
+    Two,
+    Ten,
+});
+
+named_enum!(GravityOption {
+    Default,
+    Low,
+    High,
+    Super_High,
+});
+
+named_enum!(DemolishOption {
+    Default,
+    Disabled,
+    Friendly_Fire,
+    On_Contact,
+    On_Contact_FF,
+});
+
+named_enum!(RespawnTimeOption {
+    Three_Seconds,
+    Two_Seconds,
+    One_Seconds,
+    Disable_Goal_Reset,
+});
+
+named_enum!(ExistingMatchBehavior {
+    Restart_If_Different,
+    Restart,
+    Continue_And_Spawn,
+});
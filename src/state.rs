@@ -8,6 +8,8 @@ use crate::{
 
 /// Represents a vector in 3D space.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct Vector3Partial {
     /// The X coordinate.
     pub x: Option<f32>,
@@ -15,6 +17,7 @@ pub struct Vector3Partial {
     pub y: Option<f32>,
     /// The Z coordinate.
     pub z: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -60,6 +63,8 @@ impl Vector3Partial {
 
 /// A rotation in 3D space represented by Euler angles.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct RotatorPartial {
     /// The pitch.
     pub pitch: Option<f32>,
@@ -67,6 +72,7 @@ pub struct RotatorPartial {
     pub yaw: Option<f32>,
     /// The roll.
     pub roll: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -112,6 +118,8 @@ impl RotatorPartial {
 
 /// Rigid-body state which can be set.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesiredPhysics {
     /// The location of the rigid body.
     pub location: Option<Vector3Partial>,
@@ -121,6 +129,7 @@ pub struct DesiredPhysics {
     pub velocity: Option<Vector3Partial>,
     /// The angular velocity of the rigid body.
     pub angular_velocity: Option<Vector3Partial>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -170,9 +179,12 @@ impl DesiredPhysics {
 
 /// State which can be set on the ball.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesiredBallState {
     /// The ball's physics.
     pub physics: Option<DesiredPhysics>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -201,6 +213,8 @@ impl DesiredBallState {
 
 /// State which can be set on a car.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesiredCarState {
     /// The car's physics.
     pub physics: Option<DesiredPhysics>,
@@ -210,6 +224,7 @@ pub struct DesiredCarState {
     pub jumped: Option<bool>,
     /// Whether the car has used its second jump.
     pub double_jumped: Option<bool>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -262,9 +277,12 @@ impl DesiredCarState {
 
 /// State which can be set for a boost pickup.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesiredBoostState {
     /// The amount of time until the boost pickup respawns.
     pub respawn_time: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -294,11 +312,14 @@ impl DesiredBoostState {
 
 /// State which can be set for a boost pickup.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesiredGameInfoState {
     /// The gravity acceleration.
     pub world_gravity_z: Option<f32>,
     /// The game speed multiplier (`1.0` is normal speed).
     pub game_speed: Option<f32>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -340,6 +361,8 @@ impl DesiredGameInfoState {
 /// [`RLBot::set_game_state_struct`](crate::RLBot::set_game_state_struct) to
 /// make the magic happen.
 #[derive(Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct DesiredGameState {
     /// The state of the ball.
     pub ball_state: Option<DesiredBallState>,
@@ -349,6 +372,9 @@ pub struct DesiredGameState {
     pub boost_states: Vec<Option<DesiredBoostState>>,
     /// The state of the game environment.
     pub game_info_state: Option<DesiredGameInfoState>,
+    /// Console commands to run, in order.
+    pub console_commands: Vec<String>,
+    #[cfg_attr(feature = "serde", serde(skip))]
     non_exhaustive: (),
 }
 
@@ -388,6 +414,14 @@ impl DesiredGameState {
         self
     }
 
+    /// Adds a console command to run, e.g. `"Stat FPS"`. This can be called
+    /// more than once, and the commands run in the order they were added, as
+    /// part of the same state update.
+    pub fn console_command(mut self, command: impl Into<String>) -> Self {
+        self.console_commands.push(command.into());
+        self
+    }
+
     pub(crate) fn serialize<'a>(&self) -> flatbuffers::FlatBufferBuilder<'a> {
         let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(1024);
 
@@ -409,6 +443,19 @@ impl DesiredGameState {
                     .serialize(&mut builder)
             })
             .collect::<Vec<_>>();
+        let console_commands = self
+            .console_commands
+            .iter()
+            .map(|command| {
+                let command = builder.create_string(command);
+                flat::ConsoleCommand::create(
+                    &mut builder,
+                    &flat::ConsoleCommandArgs {
+                        command: Some(command),
+                    },
+                )
+            })
+            .collect::<Vec<_>>();
         let args = flat::DesiredGameStateArgs {
             ballState: self.ball_state.as_ref().map(|x| x.serialize(&mut builder)),
             carStates: Some(builder.create_vector(&car_states)),
@@ -417,7 +464,11 @@ impl DesiredGameState {
                 .game_info_state
                 .as_ref()
                 .map(|x| x.serialize(&mut builder)),
-            consoleCommands: None,
+            consoleCommands: if console_commands.is_empty() {
+                None
+            } else {
+                Some(builder.create_vector(&console_commands))
+            },
         };
         let root = flat::DesiredGameState::create(&mut builder, &args);
 
@@ -0,0 +1,157 @@
+//! Async adapters for [`Packeteer`] and [`Physicist`].
+//!
+//! These are only available with the `async` cargo feature.
+
+use crate::{
+    error::Error,
+    game::{GameTickPacket, RigidBodyTick},
+    packeteer::Packeteer,
+    physicist::Physicist,
+};
+use futures_core::Stream;
+use futures_timer::Delay;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+
+/// A [`Stream`] of unique [`GameTickPacket`]s, created by
+/// [`Packeteer::into_stream`].
+///
+/// Packets that cannot be decoded are yielded as errors, and the stream
+/// carries on afterwards. The stream never ends. Unlike [`Packeteer::next`],
+/// it does not give up if the game stops sending packets, so wrap it in your
+/// runtime's timeout if you need one.
+///
+/// The stream borrows [`RLBot`](crate::RLBot), which is not `Sync`, so the
+/// stream is not `Send` either. Poll it on the thread that called
+/// [`init`](crate::init), e.g. with a single-threaded executor or a runtime's
+/// `spawn_local`.
+pub struct PacketStream<'a> {
+    packeteer: Packeteer<'a>,
+    poller: Poller,
+}
+
+/// A [`Stream`] of unique [`RigidBodyTick`]s, created by
+/// [`Physicist::into_stream`].
+///
+/// Ticks that cannot be decoded are yielded as errors, and the stream carries
+/// on afterwards. The stream never ends. Unlike [`Physicist::next_flat`], it
+/// does not give up if the game stops sending ticks, so wrap it in your
+/// runtime's timeout if you need one.
+///
+/// Like [`PacketStream`], this is not `Send`, and must be polled on the thread
+/// that called [`init`](crate::init).
+pub struct PhysicsStream<'a> {
+    physicist: Physicist<'a>,
+    poller: Poller,
+}
+
+impl<'a> Packeteer<'a> {
+    /// Converts this `Packeteer` into a [`Stream`] of packets, for use with
+    /// async runtimes.
+    ///
+    /// While no new packet is available, the stream sleeps without blocking
+    /// the thread, and checks again after the configured
+    /// [`poll_interval`](crate::PacketeerOptions::poll_interval).
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// # fn main() -> Result<(), Box<::std::error::Error>> {
+    /// use futures::{executor::block_on, StreamExt};
+    ///
+    /// let rlbot = rlbot::init()?;
+    /// let mut packets = rlbot.packeteer().into_stream();
+    /// block_on(async {
+    ///     while let Some(packet) = packets.next().await {
+    ///         println!("{}", packet?.game_info.seconds_elapsed);
+    ///     }
+    ///     Ok::<_, rlbot::Error>(())
+    /// })?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn into_stream(self) -> PacketStream<'a> {
+        let poller = Poller::new(self.poll_interval());
+        PacketStream {
+            packeteer: self,
+            poller,
+        }
+    }
+}
+
+impl<'a> Physicist<'a> {
+    /// Converts this `Physicist` into a [`Stream`] of physics ticks, for use
+    /// with async runtimes.
+    ///
+    /// While no new tick is available, the stream sleeps without blocking the
+    /// thread, and checks again at the same rate as
+    /// [`next_flat`](Physicist::next_flat) would.
+    pub fn into_stream(self) -> PhysicsStream<'a> {
+        PhysicsStream {
+            physicist: self,
+            poller: Poller::new(Physicist::POLL_INTERVAL),
+        }
+    }
+}
+
+impl Stream for PacketStream<'_> {
+    type Item = Result<GameTickPacket, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let packeteer = &mut this.packeteer;
+        this.poller.poll(cx, || packeteer.try_next()).map(Some)
+    }
+}
+
+impl Stream for PhysicsStream<'_> {
+    type Item = Result<RigidBodyTick, Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let physicist = &mut this.physicist;
+        this.poller.poll(cx, || physicist.try_next_flat()).map(Some)
+    }
+}
+
+/// Calls a polling function repeatedly, sleeping between attempts.
+struct Poller {
+    interval: Duration,
+    delay: Option<Delay>,
+}
+
+impl Poller {
+    fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            delay: None,
+        }
+    }
+
+    fn poll<T>(
+        &mut self,
+        cx: &mut Context<'_>,
+        mut f: impl FnMut() -> Result<Option<T>, Error>,
+    ) -> Poll<Result<T, Error>> {
+        loop {
+            if let Some(delay) = &mut self.delay {
+                match Pin::new(delay).poll(cx) {
+                    Poll::Ready(()) => self.delay = None,
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+
+            match f() {
+                Ok(Some(item)) => return Poll::Ready(Ok(item)),
+                Ok(None) => {}
+                Err(error) => return Poll::Ready(Err(error)),
+            }
+
+            self.delay = Some(Delay::new(self.interval));
+        }
+    }
+}
@@ -0,0 +1,692 @@
+//! Structural verification of flatbuffers received from RLBot.
+//!
+//! The `flatbuffers` crate trusts its input completely. Its accessors index
+//! straight into the buffer, and read strings, bools and enums without
+//! validating them, so a truncated or corrupt buffer means a panic at best.
+//! Every buffer we get from the core goes through here before any accessor
+//! touches it.
+//!
+//! Besides the shape of the buffer, we also check that every field our types
+//! rely on is present, mirroring the `TryFrom` conversions in
+//! `game_deserialize`. A verified buffer can therefore be read without any
+//! further checks, which is what the views in `game_view` depend on.
+
+use crate::{error::InvalidFlatbufferError, flat, quick_chat};
+use flatbuffers::VOffsetT;
+use std::{
+    convert::TryInto,
+    mem::{align_of, size_of},
+    str,
+};
+
+const SIZE_VECTOR3: usize = size_of::<flat::Vector3>();
+const SIZE_ROTATOR: usize = size_of::<flat::Rotator>();
+const SIZE_QUATERNION: usize = size_of::<flat::Quaternion>();
+
+/// Checks that `buf` holds a well-formed [`flat::GameTickPacket`].
+pub(crate) fn game_tick_packet(buf: &[u8]) -> Result<(), InvalidFlatbufferError> {
+    let v = Verifier::new(buf, "GameTickPacket");
+    let packet = v.root()?;
+    v.require(
+        &packet,
+        "GameTickPacket",
+        flat::GameTickPacket::VT_PLAYERS,
+        "players",
+    )?;
+    v.require(
+        &packet,
+        "GameTickPacket",
+        flat::GameTickPacket::VT_BOOSTPADSTATES,
+        "boostPadStates",
+    )?;
+    v.require(
+        &packet,
+        "GameTickPacket",
+        flat::GameTickPacket::VT_GAMEINFO,
+        "gameInfo",
+    )?;
+    v.require(
+        &packet,
+        "GameTickPacket",
+        flat::GameTickPacket::VT_TEAMS,
+        "teams",
+    )?;
+    v.tables(&packet, flat::GameTickPacket::VT_PLAYERS, player_info)?;
+    v.tables(
+        &packet,
+        flat::GameTickPacket::VT_BOOSTPADSTATES,
+        boost_pad_state,
+    )?;
+    v.table(&packet, flat::GameTickPacket::VT_BALL, ball_info)?;
+    v.table(&packet, flat::GameTickPacket::VT_GAMEINFO, game_info)?;
+    v.tables(
+        &packet,
+        flat::GameTickPacket::VT_TILEINFORMATION,
+        dropshot_tile,
+    )?;
+    v.tables(&packet, flat::GameTickPacket::VT_TEAMS, team_info)?;
+    Ok(())
+}
+
+/// Checks that `buf` holds a well-formed [`flat::FieldInfo`].
+pub(crate) fn field_info(buf: &[u8]) -> Result<(), InvalidFlatbufferError> {
+    let v = Verifier::new(buf, "FieldInfo");
+    let info = v.root()?;
+    v.require(
+        &info,
+        "FieldInfo",
+        flat::FieldInfo::VT_BOOSTPADS,
+        "boostPads",
+    )?;
+    v.require(&info, "FieldInfo", flat::FieldInfo::VT_GOALS, "goals")?;
+    v.tables(&info, flat::FieldInfo::VT_BOOSTPADS, boost_pad)?;
+    v.tables(&info, flat::FieldInfo::VT_GOALS, goal_info)?;
+    Ok(())
+}
+
+/// Checks that `buf` holds a well-formed [`flat::RigidBodyTick`].
+pub(crate) fn rigid_body_tick(buf: &[u8]) -> Result<(), InvalidFlatbufferError> {
+    let v = Verifier::new(buf, "RigidBodyTick");
+    let tick = v.root()?;
+    v.require(
+        &tick,
+        "RigidBodyTick",
+        flat::RigidBodyTick::VT_PLAYERS,
+        "players",
+    )?;
+    v.table(&tick, flat::RigidBodyTick::VT_BALL, ball_rigid_body_state)?;
+    v.tables(
+        &tick,
+        flat::RigidBodyTick::VT_PLAYERS,
+        player_rigid_body_state,
+    )?;
+    Ok(())
+}
+
+/// Checks that `buf` holds a well-formed [`flat::BallPrediction`].
+pub(crate) fn ball_prediction(buf: &[u8]) -> Result<(), InvalidFlatbufferError> {
+    let v = Verifier::new(buf, "BallPrediction");
+    let prediction = v.root()?;
+    v.require(
+        &prediction,
+        "BallPrediction",
+        flat::BallPrediction::VT_SLICES,
+        "slices",
+    )?;
+    v.tables(
+        &prediction,
+        flat::BallPrediction::VT_SLICES,
+        prediction_slice,
+    )?;
+    Ok(())
+}
+
+/// Checks that `buf` holds a well-formed [`flat::QuickChatMessages`].
+pub(crate) fn quick_chat_messages(buf: &[u8]) -> Result<(), InvalidFlatbufferError> {
+    let v = Verifier::new(buf, "QuickChatMessages");
+    let messages = v.root()?;
+    v.tables(&messages, flat::QuickChatMessages::VT_MESSAGES, quick_chat)?;
+    Ok(())
+}
+
+fn player_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(t, "PlayerInfo", flat::PlayerInfo::VT_PHYSICS, "physics")?;
+    v.require(t, "PlayerInfo", flat::PlayerInfo::VT_SCOREINFO, "scoreInfo")?;
+    v.require(t, "PlayerInfo", flat::PlayerInfo::VT_NAME, "name")?;
+    v.table(t, flat::PlayerInfo::VT_PHYSICS, physics)?;
+    v.table(t, flat::PlayerInfo::VT_SCOREINFO, score_info)?;
+    v.bool(t, flat::PlayerInfo::VT_ISDEMOLISHED)?;
+    v.bool(t, flat::PlayerInfo::VT_HASWHEELCONTACT)?;
+    v.bool(t, flat::PlayerInfo::VT_ISSUPERSONIC)?;
+    v.bool(t, flat::PlayerInfo::VT_ISBOT)?;
+    v.bool(t, flat::PlayerInfo::VT_JUMPED)?;
+    v.bool(t, flat::PlayerInfo::VT_DOUBLEJUMPED)?;
+    v.string(t, flat::PlayerInfo::VT_NAME)?;
+    v.scalar::<i32>(t, flat::PlayerInfo::VT_TEAM)?;
+    v.scalar::<i32>(t, flat::PlayerInfo::VT_BOOST)?;
+    v.table(t, flat::PlayerInfo::VT_HITBOX, box_shape)?;
+    Ok(())
+}
+
+fn score_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_SCORE)?;
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_GOALS)?;
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_OWNGOALS)?;
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_ASSISTS)?;
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_SAVES)?;
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_SHOTS)?;
+    v.scalar::<i32>(t, flat::ScoreInfo::VT_DEMOLITIONS)?;
+    Ok(())
+}
+
+fn boost_pad_state(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.bool(t, flat::BoostPadState::VT_ISACTIVE)?;
+    v.scalar::<f32>(t, flat::BoostPadState::VT_TIMER)?;
+    Ok(())
+}
+
+fn ball_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(t, "BallInfo", flat::BallInfo::VT_PHYSICS, "physics")?;
+    v.table(t, flat::BallInfo::VT_PHYSICS, physics)?;
+    v.table(t, flat::BallInfo::VT_LATESTTOUCH, touch)?;
+    v.table(t, flat::BallInfo::VT_DROPSHOTINFO, dropshot_ball_info)?;
+    let shape_type = v.enum_value(t, flat::BallInfo::VT_SHAPE_TYPE, 3)?;
+    match shape_type {
+        Some(1) => v.table(t, flat::BallInfo::VT_SHAPE, box_shape)?,
+        Some(2) => v.table(t, flat::BallInfo::VT_SHAPE, sphere_shape)?,
+        Some(3) => v.table(t, flat::BallInfo::VT_SHAPE, cylinder_shape)?,
+        _ => v.table(t, flat::BallInfo::VT_SHAPE, |_, _| Ok(()))?,
+    }
+    Ok(())
+}
+
+fn box_shape(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<f32>(t, flat::BoxShape::VT_LENGTH)?;
+    v.scalar::<f32>(t, flat::BoxShape::VT_WIDTH)?;
+    v.scalar::<f32>(t, flat::BoxShape::VT_HEIGHT)?;
+    Ok(())
+}
+
+fn sphere_shape(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<f32>(t, flat::SphereShape::VT_DIAMETER)?;
+    Ok(())
+}
+
+fn cylinder_shape(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<f32>(t, flat::CylinderShape::VT_DIAMETER)?;
+    v.scalar::<f32>(t, flat::CylinderShape::VT_HEIGHT)?;
+    Ok(())
+}
+
+fn physics(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(t, "Physics", flat::Physics::VT_LOCATION, "location")?;
+    v.require(t, "Physics", flat::Physics::VT_VELOCITY, "velocity")?;
+    v.require(
+        t,
+        "Physics",
+        flat::Physics::VT_ANGULARVELOCITY,
+        "angularVelocity",
+    )?;
+    v.struct_field(t, flat::Physics::VT_LOCATION, SIZE_VECTOR3)?;
+    v.struct_field(t, flat::Physics::VT_ROTATION, SIZE_ROTATOR)?;
+    v.struct_field(t, flat::Physics::VT_VELOCITY, SIZE_VECTOR3)?;
+    v.struct_field(t, flat::Physics::VT_ANGULARVELOCITY, SIZE_VECTOR3)?;
+    Ok(())
+}
+
+fn touch(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(t, "Touch", flat::Touch::VT_PLAYERNAME, "playerName")?;
+    v.require(t, "Touch", flat::Touch::VT_LOCATION, "location")?;
+    v.require(t, "Touch", flat::Touch::VT_NORMAL, "normal")?;
+    v.string(t, flat::Touch::VT_PLAYERNAME)?;
+    v.scalar::<f32>(t, flat::Touch::VT_GAMESECONDS)?;
+    v.struct_field(t, flat::Touch::VT_LOCATION, SIZE_VECTOR3)?;
+    v.struct_field(t, flat::Touch::VT_NORMAL, SIZE_VECTOR3)?;
+    v.scalar::<i32>(t, flat::Touch::VT_TEAM)?;
+    v.scalar::<i32>(t, flat::Touch::VT_PLAYERINDEX)?;
+    Ok(())
+}
+
+fn dropshot_ball_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<f32>(t, flat::DropShotBallInfo::VT_ABSORBEDFORCE)?;
+    v.scalar::<i32>(t, flat::DropShotBallInfo::VT_DAMAGEINDEX)?;
+    v.scalar::<f32>(t, flat::DropShotBallInfo::VT_FORCEACCUMRECENT)?;
+    Ok(())
+}
+
+fn game_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<f32>(t, flat::GameInfo::VT_SECONDSELAPSED)?;
+    v.scalar::<f32>(t, flat::GameInfo::VT_GAMETIMEREMAINING)?;
+    v.bool(t, flat::GameInfo::VT_ISOVERTIME)?;
+    v.bool(t, flat::GameInfo::VT_ISUNLIMITEDTIME)?;
+    v.bool(t, flat::GameInfo::VT_ISROUNDACTIVE)?;
+    v.bool(t, flat::GameInfo::VT_ISKICKOFFPAUSE)?;
+    v.bool(t, flat::GameInfo::VT_ISMATCHENDED)?;
+    v.scalar::<f32>(t, flat::GameInfo::VT_WORLDGRAVITYZ)?;
+    v.scalar::<f32>(t, flat::GameInfo::VT_GAMESPEED)?;
+    Ok(())
+}
+
+fn dropshot_tile(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.enum_value(t, flat::DropshotTile::VT_TILESTATE, 3)?;
+    Ok(())
+}
+
+fn team_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<i32>(t, flat::TeamInfo::VT_TEAMINDEX)?;
+    v.scalar::<i32>(t, flat::TeamInfo::VT_SCORE)?;
+    Ok(())
+}
+
+fn boost_pad(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(t, "BoostPad", flat::BoostPad::VT_LOCATION, "location")?;
+    v.struct_field(t, flat::BoostPad::VT_LOCATION, SIZE_VECTOR3)?;
+    v.bool(t, flat::BoostPad::VT_ISFULLBOOST)?;
+    Ok(())
+}
+
+fn goal_info(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(t, "GoalInfo", flat::GoalInfo::VT_LOCATION, "location")?;
+    v.require(t, "GoalInfo", flat::GoalInfo::VT_DIRECTION, "direction")?;
+    v.scalar::<i32>(t, flat::GoalInfo::VT_TEAMNUM)?;
+    v.struct_field(t, flat::GoalInfo::VT_LOCATION, SIZE_VECTOR3)?;
+    v.struct_field(t, flat::GoalInfo::VT_DIRECTION, SIZE_VECTOR3)?;
+    v.scalar::<f32>(t, flat::GoalInfo::VT_WIDTH)?;
+    v.scalar::<f32>(t, flat::GoalInfo::VT_HEIGHT)?;
+    Ok(())
+}
+
+fn rigid_body_state(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(
+        t,
+        "RigidBodyState",
+        flat::RigidBodyState::VT_LOCATION,
+        "location",
+    )?;
+    v.require(
+        t,
+        "RigidBodyState",
+        flat::RigidBodyState::VT_ROTATION,
+        "rotation",
+    )?;
+    v.require(
+        t,
+        "RigidBodyState",
+        flat::RigidBodyState::VT_VELOCITY,
+        "velocity",
+    )?;
+    v.require(
+        t,
+        "RigidBodyState",
+        flat::RigidBodyState::VT_ANGULARVELOCITY,
+        "angularVelocity",
+    )?;
+    v.scalar::<i32>(t, flat::RigidBodyState::VT_FRAME)?;
+    v.struct_field(t, flat::RigidBodyState::VT_LOCATION, SIZE_VECTOR3)?;
+    v.struct_field(t, flat::RigidBodyState::VT_ROTATION, SIZE_QUATERNION)?;
+    v.struct_field(t, flat::RigidBodyState::VT_VELOCITY, SIZE_VECTOR3)?;
+    v.struct_field(t, flat::RigidBodyState::VT_ANGULARVELOCITY, SIZE_VECTOR3)?;
+    Ok(())
+}
+
+fn player_rigid_body_state(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(
+        t,
+        "PlayerRigidBodyState",
+        flat::PlayerRigidBodyState::VT_STATE,
+        "state",
+    )?;
+    v.require(
+        t,
+        "PlayerRigidBodyState",
+        flat::PlayerRigidBodyState::VT_INPUT,
+        "input",
+    )?;
+    v.table(t, flat::PlayerRigidBodyState::VT_STATE, rigid_body_state)?;
+    v.table(t, flat::PlayerRigidBodyState::VT_INPUT, controller_state)?;
+    Ok(())
+}
+
+fn quick_chat(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    let max = quick_chat::MAX_SELECTION as u8;
+    v.enum_value(t, flat::QuickChat::VT_QUICKCHATSELECTION, max)?;
+    v.scalar::<i32>(t, flat::QuickChat::VT_PLAYERINDEX)?;
+    v.bool(t, flat::QuickChat::VT_TEAMONLY)?;
+    v.scalar::<i32>(t, flat::QuickChat::VT_MESSAGEINDEX)?;
+    v.scalar::<f32>(t, flat::QuickChat::VT_TIMESTAMP)?;
+    Ok(())
+}
+
+fn ball_rigid_body_state(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.table(t, flat::BallRigidBodyState::VT_STATE, rigid_body_state)?;
+    Ok(())
+}
+
+fn controller_state(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.scalar::<f32>(t, flat::ControllerState::VT_THROTTLE)?;
+    v.scalar::<f32>(t, flat::ControllerState::VT_STEER)?;
+    v.scalar::<f32>(t, flat::ControllerState::VT_PITCH)?;
+    v.scalar::<f32>(t, flat::ControllerState::VT_YAW)?;
+    v.scalar::<f32>(t, flat::ControllerState::VT_ROLL)?;
+    v.bool(t, flat::ControllerState::VT_JUMP)?;
+    v.bool(t, flat::ControllerState::VT_BOOST)?;
+    v.bool(t, flat::ControllerState::VT_HANDBRAKE)?;
+    v.bool(t, flat::ControllerState::VT_USEITEM)?;
+    Ok(())
+}
+
+fn prediction_slice(v: &Verifier<'_>, t: &Table) -> Result<(), InvalidFlatbufferError> {
+    v.require(
+        t,
+        "PredictionSlice",
+        flat::PredictionSlice::VT_PHYSICS,
+        "physics",
+    )?;
+    v.scalar::<f32>(t, flat::PredictionSlice::VT_GAMESECONDS)?;
+    v.table(t, flat::PredictionSlice::VT_PHYSICS, physics)?;
+    Ok(())
+}
+
+/// A table whose header and vtable have already been bounds-checked.
+struct Table {
+    pos: usize,
+    len: usize,
+    vtable: usize,
+    vtable_len: usize,
+}
+
+struct Verifier<'buf> {
+    buf: &'buf [u8],
+    root_type: &'static str,
+}
+
+impl<'buf> Verifier<'buf> {
+    fn new(buf: &'buf [u8], root_type: &'static str) -> Self {
+        Self { buf, root_type }
+    }
+
+    fn malformed(&self, offset: usize) -> InvalidFlatbufferError {
+        InvalidFlatbufferError::Malformed {
+            root_type: self.root_type,
+            offset,
+        }
+    }
+
+    /// Returns the bytes in `pos..pos + len`, if they are in bounds.
+    fn bytes(&self, pos: usize, len: usize) -> Result<&'buf [u8], InvalidFlatbufferError> {
+        pos.checked_add(len)
+            .and_then(|end| self.buf.get(pos..end))
+            .ok_or_else(|| self.malformed(pos))
+    }
+
+    /// Checks that a value of the given size and alignment fits at `pos`.
+    fn check(&self, pos: usize, size: usize, align: usize) -> Result<(), InvalidFlatbufferError> {
+        if pos & (align - 1) != 0 {
+            return Err(self.malformed(pos));
+        }
+        self.bytes(pos, size).map(|_| ())
+    }
+
+    fn read_u16(&self, pos: usize) -> Result<u16, InvalidFlatbufferError> {
+        self.check(pos, 2, 2)?;
+        Ok(u16::from_le_bytes(self.bytes(pos, 2)?.try_into().unwrap()))
+    }
+
+    fn read_u32(&self, pos: usize) -> Result<u32, InvalidFlatbufferError> {
+        self.check(pos, 4, 4)?;
+        Ok(u32::from_le_bytes(self.bytes(pos, 4)?.try_into().unwrap()))
+    }
+
+    fn read_i32(&self, pos: usize) -> Result<i32, InvalidFlatbufferError> {
+        Ok(self.read_u32(pos)? as i32)
+    }
+
+    fn root(&self) -> Result<Table, InvalidFlatbufferError> {
+        let offset = self.read_u32(0)?;
+        self.table_at(offset as usize)
+    }
+
+    fn table_at(&self, pos: usize) -> Result<Table, InvalidFlatbufferError> {
+        let vtable_offset = self.read_i32(pos)?;
+        let vtable = (pos as i64 - i64::from(vtable_offset)) as usize;
+        let vtable_len = self.read_u16(vtable)? as usize;
+        let len = self.read_u16(vtable + 2)? as usize;
+        if vtable_len < 4 || vtable_len & 1 != 0 || len < 4 {
+            return Err(self.malformed(vtable));
+        }
+        self.bytes(vtable, vtable_len)?;
+        self.bytes(pos, len)?;
+        Ok(Table {
+            pos,
+            len,
+            vtable,
+            vtable_len,
+        })
+    }
+
+    /// Returns the position of a field's data, or `None` if the field is
+    /// absent. Checks that `size` bytes of data fit inside the table.
+    fn field(
+        &self,
+        table: &Table,
+        field: VOffsetT,
+        size: usize,
+    ) -> Result<Option<usize>, InvalidFlatbufferError> {
+        let field = field as usize;
+        if field + 2 > table.vtable_len {
+            return Ok(None);
+        }
+        let offset = self.read_u16(table.vtable + field)? as usize;
+        if offset == 0 {
+            return Ok(None);
+        }
+        if offset + size > table.len {
+            return Err(self.malformed(table.pos + offset));
+        }
+        Ok(Some(table.pos + offset))
+    }
+
+    /// Checks that a field is present. Its contents are checked separately.
+    fn require(
+        &self,
+        table: &Table,
+        table_name: &'static str,
+        field: VOffsetT,
+        field_name: &'static str,
+    ) -> Result<(), InvalidFlatbufferError> {
+        match self.field(table, field, 0)? {
+            Some(_) => Ok(()),
+            None => Err(InvalidFlatbufferError::MissingField {
+                table: table_name,
+                field: field_name,
+            }),
+        }
+    }
+
+    fn scalar<T>(&self, table: &Table, field: VOffsetT) -> Result<(), InvalidFlatbufferError> {
+        if let Some(pos) = self.field(table, field, size_of::<T>())? {
+            self.check(pos, size_of::<T>(), align_of::<T>())?;
+        }
+        Ok(())
+    }
+
+    fn bool(&self, table: &Table, field: VOffsetT) -> Result<(), InvalidFlatbufferError> {
+        if let Some(pos) = self.field(table, field, 1)? {
+            if self.buf[pos] > 1 {
+                return Err(self.malformed(pos));
+            }
+        }
+        Ok(())
+    }
+
+    /// Checks a one-byte enum field, and returns its value if present.
+    fn enum_value(
+        &self,
+        table: &Table,
+        field: VOffsetT,
+        max: u8,
+    ) -> Result<Option<u8>, InvalidFlatbufferError> {
+        match self.field(table, field, 1)? {
+            Some(pos) if self.buf[pos] > max => Err(self.malformed(pos)),
+            Some(pos) => Ok(Some(self.buf[pos])),
+            None => Ok(None),
+        }
+    }
+
+    fn struct_field(
+        &self,
+        table: &Table,
+        field: VOffsetT,
+        size: usize,
+    ) -> Result<(), InvalidFlatbufferError> {
+        if let Some(pos) = self.field(table, field, size)? {
+            self.check(pos, size, 4)?;
+        }
+        Ok(())
+    }
+
+    /// Follows an offset field, and returns the position it points to.
+    fn offset(
+        &self,
+        table: &Table,
+        field: VOffsetT,
+    ) -> Result<Option<usize>, InvalidFlatbufferError> {
+        match self.field(table, field, 4)? {
+            Some(pos) => self.follow(pos).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    fn follow(&self, pos: usize) -> Result<usize, InvalidFlatbufferError> {
+        let offset = self.read_u32(pos)? as usize;
+        pos.checked_add(offset).ok_or_else(|| self.malformed(pos))
+    }
+
+    fn table(
+        &self,
+        table: &Table,
+        field: VOffsetT,
+        f: impl Fn(&Self, &Table) -> Result<(), InvalidFlatbufferError>,
+    ) -> Result<(), InvalidFlatbufferError> {
+        if let Some(pos) = self.offset(table, field)? {
+            f(self, &self.table_at(pos)?)?;
+        }
+        Ok(())
+    }
+
+    fn tables(
+        &self,
+        table: &Table,
+        field: VOffsetT,
+        f: impl Fn(&Self, &Table) -> Result<(), InvalidFlatbufferError>,
+    ) -> Result<(), InvalidFlatbufferError> {
+        if let Some(pos) = self.offset(table, field)? {
+            let len = self.read_u32(pos)? as usize;
+            let start = pos + 4;
+            self.bytes(
+                start,
+                len.checked_mul(4).ok_or_else(|| self.malformed(pos))?,
+            )?;
+            for i in 0..len {
+                let item = self.follow(start + i * 4)?;
+                f(self, &self.table_at(item)?)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn string(&self, table: &Table, field: VOffsetT) -> Result<(), InvalidFlatbufferError> {
+        if let Some(pos) = self.offset(table, field)? {
+            let len = self.read_u32(pos)? as usize;
+            // The string is followed by a NUL terminator.
+            let bytes = self.bytes(pos + 4, len + 1)?;
+            if bytes[len] != 0 || str::from_utf8(&bytes[..len]).is_err() {
+                return Err(self.malformed(pos));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{error::InvalidFlatbufferError, flat, verify};
+    use flatbuffers::{FlatBufferBuilder, WIPOffset};
+
+    fn build_packet() -> Vec<u8> {
+        build_packet_with(true)
+    }
+
+    fn build_packet_with(game_info: bool) -> Vec<u8> {
+        let mut builder = FlatBufferBuilder::new();
+        let name = builder.create_string("Chell");
+        let zero = flat::Vector3::new(0.0, 0.0, 0.0);
+        let physics = flat::Physics::create(
+            &mut builder,
+            &flat::PhysicsArgs {
+                location: Some(&flat::Vector3::new(1.0, 2.0, 3.0)),
+                velocity: Some(&zero),
+                angularVelocity: Some(&zero),
+                ..Default::default()
+            },
+        );
+        let score_info = flat::ScoreInfo::create(&mut builder, &Default::default());
+        let player = flat::PlayerInfo::create(
+            &mut builder,
+            &flat::PlayerInfoArgs {
+                physics: Some(physics),
+                scoreInfo: Some(score_info),
+                name: Some(name),
+                isBot: true,
+                ..Default::default()
+            },
+        );
+        let players = builder.create_vector(&[player]);
+        let boost_pad_states = builder.create_vector::<WIPOffset<_>>(&[]);
+        let teams = builder.create_vector::<WIPOffset<_>>(&[]);
+        let game_info = if game_info {
+            Some(flat::GameInfo::create(&mut builder, &Default::default()))
+        } else {
+            None
+        };
+        let packet = flat::GameTickPacket::create(
+            &mut builder,
+            &flat::GameTickPacketArgs {
+                players: Some(players),
+                boostPadStates: Some(boost_pad_states),
+                gameInfo: game_info,
+                teams: Some(teams),
+                ..Default::default()
+            },
+        );
+        builder.finish(packet, None);
+        builder.finished_data().to_vec()
+    }
+
+    #[test]
+    fn accepts_valid_buffer() {
+        verify::game_tick_packet(&build_packet()).unwrap();
+    }
+
+    #[test]
+    fn truncation_never_panics() {
+        let buf = build_packet();
+        for len in 0..buf.len() {
+            let _ = verify::game_tick_packet(&buf[..len]);
+        }
+        match verify::game_tick_packet(&buf[..buf.len() / 2]) {
+            Err(InvalidFlatbufferError::Malformed { .. }) => {}
+            _ => panic!("truncation was not detected"),
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_utf8() {
+        let mut buf = build_packet();
+        let pos = buf.windows(5).position(|w| w == b"Chell").unwrap();
+        buf[pos] = 0xff;
+        assert!(verify::game_tick_packet(&buf).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_bool() {
+        let good = build_packet();
+        // We don't know where `isBot` ended up, so try every candidate.
+        let rejected = (0..good.len()).filter(|&i| good[i] == 1).any(|i| {
+            let mut buf = good.clone();
+            buf[i] = 2;
+            verify::game_tick_packet(&buf).is_err()
+        });
+        assert!(rejected);
+    }
+
+    #[test]
+    fn reports_missing_field() {
+        match verify::game_tick_packet(&build_packet_with(false)) {
+            Err(InvalidFlatbufferError::MissingField { table, field }) => {
+                assert_eq!((table, field), ("GameTickPacket", "gameInfo"));
+            }
+            _ => panic!("missing field was not detected"),
+        }
+    }
+}
@@ -1,11 +1,16 @@
 #![allow(dead_code)] // Not all tests use every helper here.
 
+use rlbot::flat;
+#[cfg(windows)]
 use std::{io, panic, path::Path, process::Command, thread, time::Duration};
+#[cfg(windows)]
 use winapi::um::{
     processthreadsapi::TerminateProcess, synchapi::WaitForSingleObject, winbase::WAIT_OBJECT_0,
 };
+#[cfg(windows)]
 use winproc::Process;
 
+#[cfg(windows)]
 /// Runs a controlled copy of Rocket League alongside a closure. It will start
 /// Rocket League before the closure runs, and terminate Rocket League after
 /// the closure returns. In case of an unwind/panic, Rocket League will be
@@ -29,6 +34,7 @@ pub fn with_rocket_league<R>(f: impl FnOnce() -> R + panic::UnwindSafe) -> R {
     }
 }
 
+#[cfg(windows)]
 // I don't know of a better way to do this.
 //
 // Contributors: if the path on your system is not in this list, go ahead and
@@ -44,16 +50,19 @@ fn find_rocket_league_exe() -> Result<&'static str, &'static str> {
         .ok_or("RocketLeague.exe not found")
 }
 
+#[cfg(windows)]
 fn find_running_rocket_league() -> Option<Process> {
     Process::all()
         .unwrap()
         .find(|p| p.name().unwrap() == "RocketLeague.exe")
 }
 
+#[cfg(windows)]
 trait TerminateProcess {
     fn terminate(&self, exit_code: u32) -> Result<(), io::Error>;
 }
 
+#[cfg(windows)]
 impl TerminateProcess for Process {
     fn terminate(&self, exit_code: u32) -> Result<(), io::Error> {
         if unsafe { TerminateProcess(**self.handle(), exit_code) } == 0 {
@@ -68,10 +77,99 @@ impl TerminateProcess for Process {
 }
 
 // One is the loneliest number
-pub fn one_player_match() -> rlbot::MatchSettings<'static> {
+pub fn one_player_match() -> rlbot::MatchSettings {
     rlbot::MatchSettings::new().player_configurations(vec![rlbot::PlayerConfiguration::new(
         rlbot::PlayerClass::RLBotPlayer,
         "Chell",
         0,
     )])
 }
+
+/// Builds a minimal `GameTickPacket` flatbuffer with one car at `car_location`.
+pub fn build_game_tick_packet(seconds_elapsed: f32, car_location: (f32, f32, f32)) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(1024);
+
+    let (x, y, z) = car_location;
+    let location = flat::Vector3::new(x, y, z);
+    let zero = flat::Vector3::new(0.0, 0.0, 0.0);
+    let physics = flat::Physics::create(
+        &mut builder,
+        &flat::PhysicsArgs {
+            location: Some(&location),
+            rotation: None,
+            velocity: Some(&zero),
+            angularVelocity: Some(&zero),
+        },
+    );
+    let score_info = flat::ScoreInfo::create(&mut builder, &Default::default());
+    let name = builder.create_string("Chell");
+    let player = flat::PlayerInfo::create(
+        &mut builder,
+        &flat::PlayerInfoArgs {
+            physics: Some(physics),
+            scoreInfo: Some(score_info),
+            name: Some(name),
+            ..Default::default()
+        },
+    );
+    let players = builder.create_vector(&[player]);
+    let boost_pad_states = builder.create_vector::<flatbuffers::WIPOffset<_>>(&[]);
+    let game_info = flat::GameInfo::create(
+        &mut builder,
+        &flat::GameInfoArgs {
+            secondsElapsed: seconds_elapsed,
+            isRoundActive: true,
+            worldGravityZ: -650.0,
+            gameSpeed: 1.0,
+            ..Default::default()
+        },
+    );
+    let teams = builder.create_vector::<flatbuffers::WIPOffset<_>>(&[]);
+    let packet = flat::GameTickPacket::create(
+        &mut builder,
+        &flat::GameTickPacketArgs {
+            players: Some(players),
+            boostPadStates: Some(boost_pad_states),
+            ball: None,
+            gameInfo: Some(game_info),
+            tileInformation: None,
+            teams: Some(teams),
+        },
+    );
+
+    builder.finish(packet, None);
+    builder.finished_data().to_vec()
+}
+
+/// Builds a minimal `RigidBodyTick` flatbuffer with only a ball.
+pub fn build_rigid_body_tick(frame: i32) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::new_with_capacity(1024);
+
+    let zero = flat::Vector3::new(0.0, 0.0, 0.0);
+    let identity = flat::Quaternion::new(0.0, 0.0, 0.0, 1.0);
+    let state = flat::RigidBodyState::create(
+        &mut builder,
+        &flat::RigidBodyStateArgs {
+            frame,
+            location: Some(&zero),
+            rotation: Some(&identity),
+            velocity: Some(&zero),
+            angularVelocity: Some(&zero),
+        },
+    );
+    let ball = flat::BallRigidBodyState::create(
+        &mut builder,
+        &flat::BallRigidBodyStateArgs { state: Some(state) },
+    );
+    let players = builder.create_vector::<flatbuffers::WIPOffset<_>>(&[]);
+    let tick = flat::RigidBodyTick::create(
+        &mut builder,
+        &flat::RigidBodyTickArgs {
+            ball: Some(ball),
+            players: Some(players),
+        },
+    );
+
+    builder.finish(tick, None);
+    builder.finished_data().to_vec()
+}
@@ -0,0 +1,145 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::flat;
+use std::error::Error;
+
+mod common;
+
+#[test]
+fn mock_start_match() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    rlbot.start_match(&common::one_player_match())?;
+
+    let sent = backend.match_settings();
+    let settings = flatbuffers::get_root::<flat::MatchSettings<'_>>(&sent[0]);
+    let players = settings.playerConfigurations().unwrap();
+    assert_eq!(players.len(), 1);
+    assert_eq!(players.get(0).name(), Some("Chell"));
+    Ok(())
+}
+
+#[test]
+fn mock_set_game_state() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    rlbot.set_game_state(
+        &rlbot::DesiredGameState::new()
+            .car_state(0, rlbot::DesiredCarState::new().boost_amount(50.0)),
+    )?;
+
+    let sent = backend.game_states();
+    let state = flatbuffers::get_root::<flat::DesiredGameState<'_>>(&sent[0]);
+    let car = state.carStates().unwrap().get(0);
+    assert_eq!(car.boostAmount().map(|b| b.val()), Some(50.0));
+    Ok(())
+}
+
+#[test]
+fn mock_set_game_state_with_console_commands() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    rlbot.set_game_state(
+        &rlbot::DesiredGameState::new()
+            .ball_state(rlbot::DesiredBallState::new())
+            .console_command("Set WorldInfo TimeDilation 0.5")
+            .console_command("Stat FPS"),
+    )?;
+
+    let sent = backend.game_states();
+    let state = flatbuffers::get_root::<flat::DesiredGameState<'_>>(&sent[0]);
+    assert!(state.ballState().is_some());
+    let commands = state.consoleCommands().unwrap();
+    assert_eq!(commands.len(), 2);
+    assert_eq!(
+        commands.get(0).command(),
+        Some("Set WorldInfo TimeDilation 0.5"),
+    );
+    assert_eq!(commands.get(1).command(), Some("Stat FPS"));
+    Ok(())
+}
+
+#[test]
+fn mock_player_input() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let input = rlbot::ControllerState {
+        throttle: 1.0,
+        ..Default::default()
+    };
+    rlbot.update_player_input(3, &input)?;
+
+    let sent = backend.player_inputs();
+    let input = flatbuffers::get_root::<flat::PlayerInput<'_>>(&sent[0]);
+    assert_eq!(input.playerIndex(), 3);
+    assert_eq!(input.controllerState().unwrap().throttle(), 1.0);
+    Ok(())
+}
+
+#[test]
+fn mock_render_group() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut group = rlbot.begin_render_group(1234);
+    let green = group.color_rgb(0, 255, 0);
+    group.draw_line_2d((10.0, 10.0), (100.0, 100.0), green);
+    group.render()?;
+
+    let sent = backend.render_groups();
+    let group = flatbuffers::get_root::<flat::RenderGroup<'_>>(&sent[0]);
+    assert_eq!(group.id(), 1234);
+    assert_eq!(group.renderMessages().unwrap().len(), 1);
+    Ok(())
+}
+
+#[test]
+fn mock_quick_chat_is_rate_limited() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let selection = rlbot::QuickChatSelection::Information_IGotIt;
+
+    // A chat that RLBot rejects doesn't use up one of the five.
+    backend.set_status(rlbot::ffi::RLBotCoreStatus::InvalidPlayerIndex);
+    match rlbot.send_quick_chat(1, selection, true) {
+        Err(rlbot::Error::CoreStatus(_)) => {}
+        _ => panic!("expected the chat to fail"),
+    }
+    backend.set_status(rlbot::ffi::RLBotCoreStatus::Success);
+
+    for _ in 0..5 {
+        rlbot.send_quick_chat(1, selection, true)?;
+    }
+    match rlbot.send_quick_chat(1, selection, true) {
+        Err(rlbot::Error::QuickChatRateLimited(_)) => {}
+        _ => panic!("expected the sixth chat to be dropped"),
+    }
+    rlbot.send_quick_chat(2, selection, false)?;
+
+    let sent = backend.quick_chats();
+    assert_eq!(sent.len(), 6);
+    let chat = flatbuffers::get_root::<flat::QuickChat<'_>>(&sent[0]);
+    assert_eq!(chat.playerIndex(), 1);
+    assert_eq!(chat.quickChatSelection(), selection);
+    assert!(chat.teamOnly());
+    Ok(())
+}
+
+#[test]
+fn mock_core_status_is_returned() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    backend.set_status(rlbot::ffi::RLBotCoreStatus::InvalidPlayerIndex);
+    let result = rlbot.update_player_input(99, &Default::default());
+    assert!(result.is_err());
+    assert!(backend.player_inputs().is_empty());
+    Ok(())
+}
@@ -0,0 +1,97 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::{flat, Color, DebugDraw};
+use std::{error::Error, time::Duration};
+
+mod common;
+
+/// Draws through `DebugDraw` from deep inside its tick, toggling things as
+/// time goes on.
+struct DoodleBot;
+
+impl rlbot::Bot for DoodleBot {
+    fn set_player_index(&mut self, _index: usize) {}
+
+    fn tick(&mut self, packet: &rlbot::GameTickPacket) -> rlbot::ControllerState {
+        let seconds = packet.game_info.seconds_elapsed;
+        if seconds == 1.0 {
+            // Skip a tick, so the stale drawing is flushed while disabled.
+            return Default::default();
+        }
+        if seconds == 1.5 {
+            DebugDraw::set_enabled(true);
+        }
+        if seconds == 2.5 {
+            DebugDraw::set_category_enabled("b", false);
+        }
+        if seconds == 3.0 {
+            DebugDraw::set_enabled(false);
+        }
+        doodle(seconds);
+        Default::default()
+    }
+}
+
+fn doodle(seconds: f32) {
+    DebugDraw::draw("a", move |group| {
+        group.draw_line_2d((seconds, 0.0), (seconds, 10.0), Color::RED);
+    });
+    DebugDraw::draw("b", |group| {
+        group.draw_string_2d((0.0, 0.0), (1, 1), "hello", Color::WHITE);
+    });
+}
+
+#[test]
+fn mock_debug_draw() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    for &seconds in &[1.0, 1.5, 2.0, 2.5, 3.0] {
+        backend.push_live_data_packet(common::build_game_tick_packet(seconds, (0.0, 0.0, 17.0)));
+    }
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    // Nothing is queued while drawing is disabled.
+    assert!(!DebugDraw::is_enabled());
+    DebugDraw::draw("a", |_| panic!("this should never be drawn"));
+
+    // Nor is anything queued just before drawing is disabled.
+    DebugDraw::set_enabled(true);
+    DebugDraw::draw("a", |_| panic!("this is stale"));
+    DebugDraw::set_enabled(false);
+
+    let options = rlbot::RunOptions::new()
+        .packeteer_options(rlbot::PacketeerOptions::new().timeout(Duration::from_millis(50)));
+    match rlbot::run_bot_loop(&rlbot, 0, &mut DoodleBot, &options) {
+        Err(rlbot::Error::Timeout(_)) => {}
+        _ => panic!("expected the loop to time out"),
+    }
+
+    let sent: Vec<_> = backend
+        .render_groups()
+        .iter()
+        .map(|data| {
+            let group = flatbuffers::get_root::<flat::RenderGroup<'_>>(data);
+            (group.id(), group.renderMessages().map_or(0, |m| m.len()))
+        })
+        .collect();
+    let (a, b) = (sent[0].0, sent[1].0);
+    assert_ne!(a, b);
+    assert_eq!(
+        sent,
+        vec![
+            // Both categories are drawn in their own group.
+            (a, 1),
+            (b, 1),
+            // Only the category that changed is resent.
+            (a, 1),
+            // Switching off a category clears it.
+            (a, 1),
+            (b, 0),
+            // Switching off drawing clears everything.
+            (a, 0),
+        ],
+    );
+    assert!(!DebugDraw::is_category_enabled("b"));
+    Ok(())
+}
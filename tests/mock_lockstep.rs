@@ -0,0 +1,245 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::{ffi::RLBotCoreStatus, flat, CoreBackend};
+use std::{
+    cell::Cell,
+    error::Error,
+    thread::sleep,
+    time::{Duration, Instant},
+};
+
+mod common;
+
+/// Records every packet it sees, and is slow to answer the packet at
+/// `slow_at`.
+struct SlowBot {
+    seen: Vec<f32>,
+    slow_at: f32,
+}
+
+impl rlbot::Bot for SlowBot {
+    fn set_player_index(&mut self, _index: usize) {}
+
+    fn tick(&mut self, packet: &rlbot::GameTickPacket) -> rlbot::ControllerState {
+        let seconds = packet.game_info.seconds_elapsed;
+        self.seen.push(seconds);
+        if seconds == self.slow_at {
+            sleep(Duration::from_millis(20));
+        }
+        Default::default()
+    }
+}
+
+fn lockstep_options() -> rlbot::RunOptions {
+    rlbot::RunOptions::new()
+        .lockstep(true)
+        .tick_deadline(Duration::from_millis(10))
+        .packeteer_options(rlbot::PacketeerOptions::new().timeout(Duration::from_millis(50)))
+}
+
+fn push_packets(backend: &rlbot::MockBackend) {
+    for &seconds in &[1.0, 1.5, 2.0, 2.5] {
+        backend.push_live_data_packet(common::build_game_tick_packet(seconds, (0.0, 0.0, 17.0)));
+    }
+}
+
+#[test]
+fn mock_lockstep_carries_on_after_timing_violation() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    push_packets(&backend);
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut bot = SlowBot {
+        seen: Vec::new(),
+        slow_at: 2.0,
+    };
+    match rlbot::run_bot_loop(&rlbot, 0, &mut bot, &lockstep_options()) {
+        Err(rlbot::Error::Timeout(_)) => {}
+        _ => panic!("expected the loop to time out"),
+    }
+    assert_eq!(bot.seen, vec![1.0, 1.5, 2.0, 2.5]);
+    assert_eq!(backend.player_inputs().len(), 4);
+    Ok(())
+}
+
+/// Stops the game loop as soon as it misses a deadline.
+struct StrictBot(SlowBot);
+
+impl rlbot::Bot for StrictBot {
+    fn set_player_index(&mut self, index: usize) {
+        self.0.set_player_index(index);
+    }
+
+    fn tick(&mut self, packet: &rlbot::GameTickPacket) -> rlbot::ControllerState {
+        self.0.tick(packet)
+    }
+
+    fn timing_violation(&mut self, violation: rlbot::TimingViolation) -> Result<(), rlbot::Error> {
+        Err(rlbot::Error::TimingViolation(violation))
+    }
+}
+
+#[test]
+fn mock_lockstep_can_stop_on_timing_violation() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    push_packets(&backend);
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut bot = StrictBot(SlowBot {
+        seen: Vec::new(),
+        slow_at: 2.0,
+    });
+    match rlbot::run_bot_loop(&rlbot, 0, &mut bot, &lockstep_options()) {
+        Err(rlbot::Error::TimingViolation(violation)) => {
+            assert_eq!(violation.game_seconds, 2.0);
+            assert_eq!(violation.deadline, Duration::from_millis(10));
+            assert!(violation.elapsed > violation.deadline);
+        }
+        _ => panic!("expected a timing violation"),
+    }
+
+    // The slow packet was still answered before the loop stopped.
+    assert_eq!(bot.0.seen, vec![1.0, 1.5, 2.0]);
+    assert_eq!(backend.player_inputs().len(), 3);
+    Ok(())
+}
+
+/// Moves on to a new packet every `period`, whether or not anybody answered
+/// the last one. The clock starts at the first fetch.
+struct ClockBackend {
+    start: Cell<Option<Instant>>,
+    period: Duration,
+    packets: Vec<Vec<u8>>,
+}
+
+impl CoreBackend for ClockBackend {
+    fn is_initialized(&self) -> bool {
+        true
+    }
+    fn update_field_info_flatbuffer(&self) -> Option<Vec<u8>> {
+        None
+    }
+    fn update_live_data_packet_flatbuffer(&self) -> Option<Vec<u8>> {
+        let start = self.start.get().unwrap_or_else(Instant::now);
+        self.start.set(Some(start));
+        let frame = (start.elapsed().as_nanos() / self.period.as_nanos()) as usize;
+        Some(self.packets[frame.min(self.packets.len() - 1)].clone())
+    }
+    fn update_rigid_body_tick_flatbuffer(&self) -> Option<Vec<u8>> {
+        None
+    }
+    fn get_ball_prediction(&self) -> Option<Vec<u8>> {
+        None
+    }
+    fn set_game_state(&self, _: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+    fn start_match_flatbuffer(&self, _: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+    fn send_quick_chat(&self, _: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+    fn update_player_input_flatbuffer(&self, _: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+    fn render_group(&self, _: &[u8]) -> RLBotCoreStatus {
+        RLBotCoreStatus::Success
+    }
+}
+
+#[test]
+fn lockstep_polls_without_waiting_out_the_interval() -> Result<(), Box<dyn Error>> {
+    let seconds = [1.0, 1.5, 2.0, 2.5, 3.0];
+    let backend = ClockBackend {
+        start: Cell::new(None),
+        period: Duration::from_millis(20),
+        packets: seconds
+            .iter()
+            .map(|&s| common::build_game_tick_packet(s, (0.0, 0.0, 17.0)))
+            .collect(),
+    };
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    // Packets come far more often than the poll interval, so only polling
+    // straight away catches all of them.
+    let options = lockstep_options().packeteer_options(
+        rlbot::PacketeerOptions::new()
+            .poll_interval(Duration::from_millis(100))
+            .timeout(Duration::from_millis(100)),
+    );
+    let mut bot = SlowBot {
+        seen: Vec::new(),
+        slow_at: -1.0,
+    };
+    match rlbot::run_bot_loop(&rlbot, 0, &mut bot, &options) {
+        Err(rlbot::Error::Timeout(_)) => {}
+        _ => panic!("expected the loop to time out"),
+    }
+    assert_eq!(bot.seen, seconds);
+    Ok(())
+}
+
+#[test]
+fn mock_without_lockstep_ignores_deadline() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut bot = SlowBot {
+        seen: Vec::new(),
+        slow_at: 1.0,
+    };
+    let options = lockstep_options().lockstep(false);
+    match rlbot::run_bot_loop(&rlbot, 0, &mut bot, &options) {
+        Err(rlbot::Error::Timeout(_)) => {}
+        _ => panic!("expected the loop to time out"),
+    }
+    assert_eq!(bot.seen, vec![1.0]);
+    Ok(())
+}
+
+/// Only ever controls its first drone.
+struct LazyHive;
+
+impl rlbot::Hivemind for LazyHive {
+    fn set_drone_indices(&mut self, _indices: Vec<usize>) {}
+
+    fn tick(&mut self, _packet: &rlbot::GameTickPacket) -> Vec<(usize, rlbot::ControllerState)> {
+        vec![(
+            1,
+            rlbot::ControllerState {
+                throttle: 1.0,
+                ..Default::default()
+            },
+        )]
+    }
+}
+
+#[test]
+fn mock_lockstep_hive_answers_every_drone() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    match rlbot::run_hive_loop(&rlbot, &[1, 2], &mut LazyHive, &lockstep_options()) {
+        Err(rlbot::Error::Timeout(_)) => {}
+        _ => panic!("expected the loop to time out"),
+    }
+
+    let sent: Vec<_> = backend
+        .player_inputs()
+        .iter()
+        .map(|buf| {
+            let input = flatbuffers::get_root::<flat::PlayerInput<'_>>(buf);
+            (
+                input.playerIndex(),
+                input.controllerState().unwrap().throttle(),
+            )
+        })
+        .collect();
+    assert_eq!(sent, vec![(1, 1.0), (2, 0.0)]);
+    Ok(())
+}
@@ -0,0 +1,58 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use std::error::Error;
+
+mod common;
+
+#[test]
+fn packet_view_matches_owned_packet() -> Result<(), Box<dyn Error>> {
+    let buf = common::build_game_tick_packet(1.5, (10.0, 20.0, 17.0));
+    let view = rlbot::GameTickPacketView::from_flatbuffer(&buf)?;
+    let owned = view.to_packet();
+
+    assert_eq!(
+        view.game_info().seconds_elapsed,
+        owned.game_info.seconds_elapsed
+    );
+    assert_eq!(view.players().len(), owned.players.len());
+    for (player, owned_player) in view.players().iter().zip(&owned.players) {
+        assert_eq!(player.name(), owned_player.name);
+        assert_eq!(player.physics().location.x, owned_player.physics.location.x);
+        assert_eq!(player.physics().location.y, owned_player.physics.location.y);
+        assert_eq!(player.team(), owned_player.team);
+    }
+    assert!(view.players().get(1).is_none());
+    assert!(view.ball().is_none());
+    assert!(view.tile_information().is_none());
+    assert!(view.boost_pad_states().is_empty());
+    Ok(())
+}
+
+#[test]
+fn packet_view_rejects_truncated_buffer() {
+    let mut buf = common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0));
+    buf.truncate(buf.len() / 2);
+    assert!(rlbot::GameTickPacketView::from_flatbuffer(&buf).is_err());
+}
+
+#[test]
+fn mock_packeteer_views_skip_duplicate_packets() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    backend.push_live_data_packet(common::build_game_tick_packet(1.5, (0.0, 100.0, 17.0)));
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    let mut packeteer = rlbot.packeteer();
+    let first = packeteer.next_view()?;
+    assert_eq!(first.game_info().seconds_elapsed, 1.0);
+    let second = packeteer.next_view()?;
+    assert_eq!(second.game_info().seconds_elapsed, 1.5);
+    let player = second.players().get(0).unwrap();
+    assert_eq!(player.name(), "Chell");
+    assert_eq!(player.physics().location.y, 100.0);
+    assert!(packeteer.try_next_view()?.is_none());
+    Ok(())
+}
@@ -0,0 +1,129 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::flat;
+use std::{error::Error, time::Duration};
+
+mod common;
+
+#[test]
+fn mock_packeteer_skips_duplicate_packets() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    backend.push_live_data_packet(common::build_game_tick_packet(1.5, (0.0, 100.0, 17.0)));
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    let mut packeteer = rlbot.packeteer();
+    let first = packeteer.next()?;
+    let second = packeteer.next()?;
+    assert_eq!(first.game_info.seconds_elapsed, 1.0);
+    assert_eq!(second.game_info.seconds_elapsed, 1.5);
+    assert_eq!(second.players[0].physics.location.y, 100.0);
+    assert!(packeteer.try_next()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn mock_wait_for_match_start() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    for i in 0..5 {
+        backend.push_live_data_packet(common::build_game_tick_packet(
+            i as f32 + 1.0,
+            (0.0, 0.0, 0.0),
+        ));
+    }
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    rlbot.wait_for_match_start()?;
+    Ok(())
+}
+
+#[test]
+fn mock_packeteer_times_out() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    let options = rlbot::PacketeerOptions::new().timeout(Duration::from_millis(50));
+    let mut packeteer = rlbot.packeteer_with(options);
+    packeteer.next()?;
+    match packeteer.next() {
+        Err(rlbot::Error::Timeout(error)) => assert_eq!(error.timeout, Duration::from_millis(50)),
+        _ => panic!("expected a timeout"),
+    }
+    Ok(())
+}
+
+#[test]
+fn mock_adaptive_packeteer_yields_every_packet() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    for i in 1..=10 {
+        let seconds_elapsed = i as f32 / 120.0;
+        backend.push_live_data_packet(common::build_game_tick_packet(
+            seconds_elapsed,
+            (0.0, 0.0, 17.0),
+        ));
+    }
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    let options = rlbot::PacketeerOptions::new()
+        .poll_interval(Duration::from_millis(1))
+        .adaptive(true);
+    let mut packeteer = rlbot.packeteer_with(options);
+    for i in 1..=10 {
+        let packet = packeteer.next()?;
+        assert_eq!(packet.game_info.seconds_elapsed, i as f32 / 120.0);
+    }
+    Ok(())
+}
+
+#[test]
+fn mock_packeteer_reports_truncated_packet() -> Result<(), Box<dyn Error>> {
+    let mut packet = common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0));
+    packet.truncate(packet.len() / 2);
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(packet);
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    match rlbot.packeteer().next() {
+        Err(rlbot::Error::InvalidFlatbuffer(rlbot::InvalidFlatbufferError::Malformed {
+            root_type: "GameTickPacket",
+            ..
+        })) => {}
+        _ => panic!("expected a malformed packet error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn mock_packeteer_reports_missing_field() -> Result<(), Box<dyn Error>> {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let players = builder.create_vector::<flatbuffers::WIPOffset<_>>(&[]);
+    let boost_pad_states = builder.create_vector::<flatbuffers::WIPOffset<_>>(&[]);
+    let teams = builder.create_vector::<flatbuffers::WIPOffset<_>>(&[]);
+    let packet = flat::GameTickPacket::create(
+        &mut builder,
+        &flat::GameTickPacketArgs {
+            players: Some(players),
+            boostPadStates: Some(boost_pad_states),
+            teams: Some(teams),
+            ..Default::default()
+        },
+    );
+    builder.finish(packet, None);
+
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(builder.finished_data());
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    match rlbot.packeteer().next() {
+        Err(rlbot::Error::InvalidFlatbuffer(rlbot::InvalidFlatbufferError::MissingField {
+            table,
+            field,
+        })) => assert_eq!((table, field), ("GameTickPacket", "gameInfo")),
+        _ => panic!("expected a missing field error"),
+    }
+    Ok(())
+}
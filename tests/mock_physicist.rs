@@ -0,0 +1,25 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use std::{error::Error, time::Duration};
+
+mod common;
+
+#[test]
+fn mock_physicist_yields_each_frame_once() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_rigid_body_tick(common::build_rigid_body_tick(10));
+    backend.push_rigid_body_tick(common::build_rigid_body_tick(11));
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    let mut physicist = rlbot.physicist();
+    let first = physicist.next_flat()?;
+    let second = physicist.next_flat()?;
+    assert_eq!(first.ball.unwrap().state.unwrap().frame, 10);
+    assert_eq!(second.ball.unwrap().state.unwrap().frame, 11);
+    assert!(physicist
+        .next_flat_with_timeout(Duration::from_millis(50))
+        .is_err());
+    Ok(())
+}
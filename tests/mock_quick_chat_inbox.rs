@@ -0,0 +1,98 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::{flat, QuickChatSelection};
+use std::{error::Error, thread, time::Duration};
+
+/// Builds a `QuickChatMessages` holding `(player_index, message_index)` pairs.
+fn build_messages(chats: &[(i32, i32)]) -> Vec<u8> {
+    let mut builder = flatbuffers::FlatBufferBuilder::new();
+    let chats: Vec<_> = chats
+        .iter()
+        .map(|&(player_index, message_index)| {
+            flat::QuickChat::create(
+                &mut builder,
+                &flat::QuickChatArgs {
+                    quickChatSelection: QuickChatSelection::Information_Defending,
+                    playerIndex: player_index,
+                    teamOnly: true,
+                    messageIndex: message_index,
+                    timeStamp: 12.5,
+                },
+            )
+        })
+        .collect();
+    let chats = builder.create_vector(&chats);
+    let root = flat::QuickChatMessages::create(
+        &mut builder,
+        &flat::QuickChatMessagesArgs {
+            messages: Some(chats),
+        },
+    );
+    builder.finish(root, None);
+    builder.finished_data().to_vec()
+}
+
+#[test]
+fn mock_inbox_yields_each_chat_once() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+    let mut inbox = rlbot.quick_chat_inbox(0, 0);
+
+    assert!(inbox.try_next()?.is_none());
+
+    backend.push_quick_chat_messages(build_messages(&[(1, 0), (0, 1)]));
+    let chat = inbox.try_next()?.unwrap();
+    assert_eq!(chat.player_index, 1);
+    assert_eq!(chat.message_index, 0);
+    assert_eq!(chat.selection, QuickChatSelection::Information_Defending);
+    assert!(chat.team_only);
+    assert_eq!(chat.game_seconds, 12.5);
+    assert_eq!(inbox.try_next()?.unwrap().message_index, 1);
+
+    // The same messages again, plus a new one.
+    backend.push_quick_chat_messages(build_messages(&[(1, 0), (0, 1), (2, 2)]));
+    assert_eq!(inbox.try_next()?.unwrap().message_index, 2);
+    assert!(inbox.try_next()?.is_none());
+    Ok(())
+}
+
+#[test]
+fn mock_inbox_can_ignore_own_chats() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+    let mut inbox = rlbot.quick_chat_inbox(0, 0).ignore_own(true);
+
+    backend.push_quick_chat_messages(build_messages(&[(0, 0), (1, 1), (0, 2)]));
+    let senders: Vec<_> = (0..3)
+        .filter_map(|_| inbox.try_next().unwrap())
+        .map(|chat| chat.player_index)
+        .collect();
+    assert_eq!(senders, vec![1]);
+    Ok(())
+}
+
+#[test]
+fn mock_inbox_next_waits_for_a_chat() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+    let mut inbox = rlbot.quick_chat_inbox(0, 0);
+
+    let pusher = thread::spawn(move || {
+        thread::sleep(Duration::from_millis(50));
+        backend.push_quick_chat_messages(build_messages(&[(1, 0)]));
+    });
+    assert_eq!(inbox.next()?.player_index, 1);
+    pusher.join().unwrap();
+
+    // Nothing new, so this should give up.
+    match inbox.next_with_timeout(Duration::from_millis(50)) {
+        Err(rlbot::Error::Timeout(_)) => {}
+        other => panic!(
+            "expected a timeout, got {:?}",
+            other.map(|c| c.message_index)
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,108 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::flat;
+use std::error::Error;
+
+#[test]
+fn mock_render_group() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut group = rlbot.begin_render_group(7);
+    let red = group.color_argb(127, 255, 0, 0);
+    group.draw_line_2d((1.0, 2.0), (3.0, 4.0), red);
+    group.draw_line_3d((1.0, 2.0, 3.0), (4.0, 5.0, 6.0), red);
+    group.draw_line_2d_3d((1.0, 2.0), (3.0, 4.0, 5.0), red);
+    group.draw_string_2d((1.0, 2.0), (3, 4), "2D", red);
+    group.draw_string_3d((1.0, 2.0, 3.0), (4, 5), "3D", red);
+    group.draw_rect_2d((1.0, 2.0), (30, 40), true, red);
+    group.draw_rect_3d((1.0, 2.0, 3.0), (40, 50), false, red);
+    group.draw_centered_rect_3d((1.0, 2.0, 3.0), (50, 60), true, red);
+    group.render()?;
+
+    let sent = backend.render_groups();
+    let group = flatbuffers::get_root::<flat::RenderGroup<'_>>(&sent[0]);
+    assert_eq!(group.id(), 7);
+    let messages = group.renderMessages().unwrap();
+    assert_eq!(messages.len(), 8);
+
+    let point = |v: Option<&flat::Vector3>| v.map(|v| (v.x(), v.y(), v.z()));
+    let summary = |i| {
+        let m = messages.get(i);
+        (
+            m.renderType(),
+            point(m.start()),
+            point(m.end()),
+            (m.scaleX(), m.scaleY()),
+            m.text(),
+            m.isFilled(),
+        )
+    };
+
+    use flat::RenderType::*;
+    let (p12, p123) = (Some((1.0, 2.0, 0.0)), Some((1.0, 2.0, 3.0)));
+    assert_eq!(
+        summary(0),
+        (DrawLine2D, p12, Some((3.0, 4.0, 0.0)), (1, 1), None, false),
+    );
+    assert_eq!(
+        summary(1),
+        (DrawLine3D, p123, Some((4.0, 5.0, 6.0)), (1, 1), None, false),
+    );
+    assert_eq!(
+        summary(2),
+        (
+            DrawLine2D_3D,
+            p12,
+            Some((3.0, 4.0, 5.0)),
+            (1, 1),
+            None,
+            false
+        ),
+    );
+    assert_eq!(
+        summary(3),
+        (DrawString2D, p12, None, (3, 4), Some("2D"), false),
+    );
+    assert_eq!(
+        summary(4),
+        (DrawString3D, p123, None, (4, 5), Some("3D"), false),
+    );
+    assert_eq!(summary(5), (DrawRect2D, p12, None, (30, 40), None, true));
+    assert_eq!(summary(6), (DrawRect3D, p123, None, (40, 50), None, false));
+    assert_eq!(
+        summary(7),
+        (DrawCenteredRect3D, p123, None, (50, 60), None, true),
+    );
+
+    for i in 0..messages.len() {
+        let color = messages.get(i).color().unwrap();
+        assert_eq!(
+            (color.a(), color.r(), color.g(), color.b()),
+            (127, 255, 0, 0)
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn mock_render_group_dedupes_colors() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut group = rlbot.begin_render_group(0);
+    group.draw_line_2d((0.0, 0.0), (1.0, 1.0), rlbot::Color::GREEN);
+    group.draw_line_2d((0.0, 0.0), (1.0, 1.0), rlbot::Color::BLUE);
+    group.draw_line_2d((0.0, 0.0), (1.0, 1.0), rlbot::Color::rgb(0, 255, 0));
+    group.render()?;
+
+    let sent = backend.render_groups();
+    let group = flatbuffers::get_root::<flat::RenderGroup<'_>>(&sent[0]);
+    let messages = group.renderMessages().unwrap();
+    let color_loc = |i| messages.get(i).color().unwrap()._tab.loc;
+    assert_eq!(color_loc(0), color_loc(2));
+    assert_ne!(color_loc(0), color_loc(1));
+    Ok(())
+}
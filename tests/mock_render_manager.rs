@@ -0,0 +1,110 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::{flat, Color, RLBot, RenderManager};
+use std::error::Error;
+
+const STRIDE: i32 = RenderManager::ID_STRIDE;
+
+/// Draws one 1000-character string per label, so that two fit in a 3000-byte
+/// group but three do not.
+fn render(
+    rlbot: &RLBot,
+    manager: &mut RenderManager,
+    labels: &[u32],
+) -> Result<(), Box<dyn Error>> {
+    let mut group = rlbot.begin_render_group(100);
+    for &label in labels {
+        let text = format!("{:>1000}", label);
+        group.draw_string_2d((0.0, 0.0), (1, 1), text, Color::GREEN);
+    }
+    manager.render(group)?;
+    Ok(())
+}
+
+/// Returns the id and message count of each group sent since the last call.
+fn drain(backend: &rlbot::MockBackend, seen: &mut usize) -> Vec<(i32, usize)> {
+    let sent = backend.render_groups();
+    let new = sent[*seen..]
+        .iter()
+        .map(|data| {
+            let group = flatbuffers::get_root::<flat::RenderGroup<'_>>(data);
+            let count = group.renderMessages().map_or(0, |m| m.len());
+            (group.id(), count)
+        })
+        .collect();
+    *seen = sent.len();
+    new
+}
+
+#[test]
+fn mock_render_manager() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+    let mut manager = RenderManager::new().max_bytes_per_group(3000);
+    let mut seen = 0;
+
+    // Oversized groups are split.
+    render(&rlbot, &mut manager, &[1, 2, 3, 4, 5])?;
+    assert_eq!(
+        drain(&backend, &mut seen),
+        vec![(100, 2), (100 + STRIDE, 2), (100 + 2 * STRIDE, 1)],
+    );
+    assert!(backend
+        .render_groups()
+        .iter()
+        .all(|data| data.len() <= 3000));
+
+    // Nothing is resent if nothing changed.
+    render(&rlbot, &mut manager, &[1, 2, 3, 4, 5])?;
+    assert_eq!(drain(&backend, &mut seen), vec![]);
+
+    // Only the changed chunk is resent.
+    render(&rlbot, &mut manager, &[1, 2, 3, 40, 5])?;
+    assert_eq!(drain(&backend, &mut seen), vec![(100 + STRIDE, 2)]);
+
+    // Chunks that are no longer needed are cleared.
+    render(&rlbot, &mut manager, &[1, 2])?;
+    assert_eq!(
+        drain(&backend, &mut seen),
+        vec![(100 + 2 * STRIDE, 0), (100 + STRIDE, 0)],
+    );
+
+    // Rendering nothing clears the first group too, but only once.
+    render(&rlbot, &mut manager, &[])?;
+    assert_eq!(drain(&backend, &mut seen), vec![(100, 0)]);
+    render(&rlbot, &mut manager, &[])?;
+    assert_eq!(drain(&backend, &mut seen), vec![]);
+
+    // Clearing forgets what was sent, so the next frame is sent in full.
+    manager.clear(&rlbot, 100)?;
+    assert_eq!(drain(&backend, &mut seen), vec![(100, 0)]);
+    render(&rlbot, &mut manager, &[1])?;
+    assert_eq!(drain(&backend, &mut seen), vec![(100, 1)]);
+    Ok(())
+}
+
+#[test]
+fn mock_render_manager_splits_long_strings() -> Result<(), Box<dyn Error>> {
+    let backend = rlbot::MockBackend::new();
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+    let mut manager = RenderManager::new();
+
+    let label = "x".repeat(500);
+    let mut group = rlbot.begin_render_group(100);
+    for i in 0..256 {
+        group.draw_string_2d((0.0, i as f32), (1, 1), &label, Color::WHITE);
+    }
+    manager.render(group)?;
+
+    let sent = backend.render_groups();
+    assert!(sent.len() > 1);
+    assert!(sent
+        .iter()
+        .all(|data| data.len() <= RenderManager::DEFAULT_MAX_BYTES));
+    let mut seen = 0;
+    let total: usize = drain(&backend, &mut seen).iter().map(|&(_, n)| n).sum();
+    assert_eq!(total, 256);
+    Ok(())
+}
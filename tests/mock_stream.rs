@@ -0,0 +1,68 @@
+#![cfg(feature = "async")]
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use futures::{executor::block_on, StreamExt, TryStreamExt};
+
+mod common;
+
+#[test]
+fn mock_packet_stream() -> Result<(), rlbot::Error> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    backend.push_live_data_packet(common::build_game_tick_packet(2.0, (0.0, 0.0, 17.0)));
+    let rlbot = rlbot::init_with_backend(backend)?;
+
+    let packets = rlbot.packeteer().into_stream();
+    let times = block_on(
+        packets
+            .take(2)
+            .map_ok(|p| p.game_info.seconds_elapsed)
+            .try_collect::<Vec<_>>(),
+    )?;
+    assert_eq!(times, vec![1.0, 2.0]);
+    Ok(())
+}
+
+#[test]
+fn mock_physics_stream_waits_for_new_ticks() -> Result<(), rlbot::Error> {
+    let backend = rlbot::MockBackend::new();
+    backend.push_rigid_body_tick(common::build_rigid_body_tick(1));
+    let rlbot = rlbot::init_with_backend(backend.clone())?;
+
+    let mut ticks = rlbot.physicist().into_stream();
+    block_on(async {
+        let first = ticks.next().await.unwrap()?;
+        assert_eq!(first.ball.unwrap().state.unwrap().frame, 1);
+
+        // Nothing new yet, so the stream should sleep until the tick shows up.
+        let pusher = std::thread::spawn(move || {
+            std::thread::sleep(std::time::Duration::from_millis(20));
+            backend.push_rigid_body_tick(common::build_rigid_body_tick(2));
+        });
+        let second = ticks.next().await.unwrap()?;
+        assert_eq!(second.ball.unwrap().state.unwrap().frame, 2);
+        pusher.join().unwrap();
+        Ok(())
+    })
+}
+
+/// The streams borrow `RLBot`, which is `!Sync`, so they must not be `Send`.
+/// They should be `Unpin`, so that `StreamExt::next` can be used directly.
+#[test]
+fn stream_auto_traits() {
+    // Resolving `some_item` is ambiguous if `T: Send`, which fails to compile.
+    trait AmbiguousIfSend<A> {
+        fn some_item() {}
+    }
+    impl<T: ?Sized> AmbiguousIfSend<()> for T {}
+    impl<T: ?Sized + Send> AmbiguousIfSend<u8> for T {}
+
+    fn assert_unpin<T: Unpin>() {}
+
+    let _ = <rlbot::PacketStream<'_> as AmbiguousIfSend<_>>::some_item;
+    let _ = <rlbot::PhysicsStream<'_> as AmbiguousIfSend<_>>::some_item;
+    assert_unpin::<rlbot::PacketStream<'_>>();
+    assert_unpin::<rlbot::PhysicsStream<'_>>();
+}
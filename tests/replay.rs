@@ -0,0 +1,45 @@
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use std::{env, error::Error, fs::File};
+
+mod common;
+
+#[test]
+fn replay_through_packeteer_and_physicist() -> Result<(), Box<dyn Error>> {
+    let path = env::temp_dir().join("rlbot_replay_through_packeteer_and_physicist.rlbotrec");
+
+    let mock = rlbot::MockBackend::new();
+    mock.push_live_data_packet(common::build_game_tick_packet(1.0, (0.0, 0.0, 17.0)));
+    mock.push_live_data_packet(common::build_game_tick_packet(2.0, (0.0, 50.0, 17.0)));
+    mock.push_rigid_body_tick(common::build_rigid_body_tick(120));
+    mock.push_rigid_body_tick(common::build_rigid_body_tick(121));
+    let recorder = rlbot::RecordingBackend::new(mock, File::create(&path)?)?;
+    let rlbot = rlbot::init_with_backend(recorder)?;
+    let mut packeteer = rlbot.packeteer();
+    packeteer.next()?;
+    packeteer.next()?;
+    let mut physicist = rlbot.physicist();
+    physicist.next_flat()?;
+    physicist.next_flat()?;
+    drop(rlbot);
+
+    let replay = rlbot::ReplayBackend::open(&path, rlbot::ReplayMode::AsFastAsPossible)?;
+    let rlbot = rlbot::init_with_backend(replay)?;
+    let mut packeteer = rlbot.packeteer();
+    assert_eq!(packeteer.next()?.game_info.seconds_elapsed, 1.0);
+    let packet = packeteer.next()?;
+    assert_eq!(packet.game_info.seconds_elapsed, 2.0);
+    assert_eq!(packet.players[0].physics.location.y, 50.0);
+    let mut physicist = rlbot.physicist();
+    assert_eq!(
+        physicist.next_flat()?.ball.unwrap().state.unwrap().frame,
+        120
+    );
+    assert_eq!(
+        physicist.next_flat()?.ball.unwrap().state.unwrap().frame,
+        121
+    );
+    Ok(())
+}
@@ -0,0 +1,97 @@
+#![cfg(feature = "serde")]
+#![warn(future_incompatible, rust_2018_compatibility, rust_2018_idioms, unused)]
+#![cfg_attr(feature = "strict", deny(warnings))]
+#![warn(clippy::all)]
+
+use rlbot::{
+    DesiredBallState, DesiredCarState, DesiredGameState, DesiredPhysics, GameMap, MatchLength,
+    MatchSettings, MutatorSettings, PlayerClass, RumbleOption, Vector3Partial,
+};
+
+#[test]
+fn match_settings_from_toml() {
+    let settings: MatchSettings = toml::from_str(
+        r#"
+        game_map = "Mannfield_Night"
+        skip_replays = true
+
+        [mutator_settings]
+        match_length = "Unlimited"
+
+        [[player_configurations]]
+        variety = "RLBotPlayer"
+        name = "Chell"
+        team = 0
+
+        [[player_configurations]]
+        variety = { PsyonixBotPlayer = { bot_skill = 0.5 } }
+        name = "Wheatley"
+        team = 1
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(settings.game_map, GameMap::Mannfield_Night);
+    assert!(settings.skip_replays);
+    assert_eq!(
+        settings.mutator_settings.match_length,
+        MatchLength::Unlimited
+    );
+    // Keys that are left out keep their default values.
+    assert_eq!(
+        settings.mutator_settings.rumble_option,
+        RumbleOption::No_Rumble
+    );
+    assert_eq!(settings.player_configurations.len(), 2);
+    assert_eq!(settings.player_configurations[1].name, "Wheatley");
+    match &settings.player_configurations[1].variety {
+        PlayerClass::PsyonixBotPlayer(p) => assert_eq!(p.bot_skill, 0.5),
+        _ => panic!("expected a psyonix bot"),
+    }
+}
+
+#[test]
+fn match_settings_round_trip() {
+    let settings = MatchSettings::rlbot_vs_allstar("Chell", "Wheatley")
+        .game_map(GameMap::AquaDome)
+        .mutator_settings(MutatorSettings::new().rumble_option(RumbleOption::Spike_Rush));
+
+    let toml = toml::to_string(&settings).unwrap();
+    assert!(toml.contains(r#"rumble_option = "Spike_Rush""#));
+    assert_eq!(toml::from_str::<MatchSettings>(&toml).unwrap(), settings);
+
+    let json = serde_json::to_string(&settings).unwrap();
+    assert_eq!(
+        serde_json::from_str::<MatchSettings>(&json).unwrap(),
+        settings
+    );
+}
+
+#[test]
+fn unknown_variant() {
+    let result = toml::from_str::<MatchSettings>(r#"game_map = "Narnia""#);
+    assert!(result.unwrap_err().to_string().contains("Narnia"));
+}
+
+#[test]
+fn desired_game_state_round_trip() {
+    let state = DesiredGameState::new()
+        .ball_state(
+            DesiredBallState::new()
+                .physics(DesiredPhysics::new().location(Vector3Partial::new().z(1000.0))),
+        )
+        .car_state(1, DesiredCarState::new().boost_amount(100.0))
+        .console_command("Stat FPS");
+
+    let json = serde_json::to_string(&state).unwrap();
+    let state: DesiredGameState = serde_json::from_str(&json).unwrap();
+
+    let location = state.ball_state.unwrap().physics.unwrap().location.unwrap();
+    assert_eq!((location.x, location.z), (None, Some(1000.0)));
+    assert!(state.car_states[0].is_none());
+    assert_eq!(
+        state.car_states[1].as_ref().unwrap().boost_amount,
+        Some(100.0)
+    );
+    assert_eq!(state.console_commands, ["Stat FPS"]);
+}